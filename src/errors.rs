@@ -2,6 +2,34 @@
 
 pub use err::*;
 
+/// Kind of an [`Error`](enum.Error.html), without its details. Unlike `Error`, whose
+/// fields differ depending on whether an allocator is available, this is the same
+/// in every configuration.
+#[non_exhaustive]
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub enum ErrorKind {
+    /// The sentence or its payload is malformed, or unsupported
+    Nmea,
+    /// The sentence checksum doesn't match its contents
+    Checksum,
+    /// More data is needed
+    Incomplete,
+    /// The payload is too short for its message type
+    MessageTooShort,
+}
+
+impl Error {
+    /// Returns the kind of error
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            Self::Nmea { .. } => ErrorKind::Nmea,
+            Self::Checksum { .. } => ErrorKind::Checksum,
+            Self::Incomplete => ErrorKind::Incomplete,
+            Self::MessageTooShort { .. } => ErrorKind::MessageTooShort,
+        }
+    }
+}
+
 #[cfg(any(feature = "std", feature = "alloc"))]
 mod err {
     use crate::lib;
@@ -14,9 +42,22 @@ mod err {
     #[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
     pub enum Error {
         //#[error("invalid NMEA sentence: '{msg}'")]
-        Nmea { msg: String },
+        Nmea {
+            msg: String,
+        },
         //#[error("checksum mismatch; expected: {expected:#X}, received: {found:#X}")]
-        Checksum { expected: u8, found: u8 },
+        Checksum {
+            expected: u8,
+            found: u8,
+        },
+        //#[error("incomplete input")]
+        Incomplete,
+        //#[error("message type {message_type} too short: {found} of {expected} bits")]
+        MessageTooShort {
+            message_type: u8,
+            expected: usize,
+            found: usize,
+        },
     }
 
     #[cfg(feature = "std")]
@@ -29,6 +70,14 @@ mod err {
                 Self::Checksum { expected, found } => {
                     format!("Checksum error; expected 0x{expected:x}, found 0x{found:x}")
                 }
+                Self::Incomplete => "Incomplete input; more data is needed".into(),
+                Self::MessageTooShort {
+                    message_type,
+                    expected,
+                    found,
+                } => format!(
+                    "Message type {message_type} too short; expected at least {expected} bits, found {found}"
+                ),
             };
             f.write_str(&msg)
         }
@@ -48,24 +97,33 @@ mod err {
 
     impl From<nom::Err<&[u8]>> for Error {
         fn from(err: nom::Err<&[u8]>) -> Self {
-            Self::Nmea {
-                msg: err.to_string(),
+            match err {
+                nom::Err::Incomplete(_) => Self::Incomplete,
+                _ => Self::Nmea {
+                    msg: err.to_string(),
+                },
             }
         }
     }
 
     impl From<nom::Err<(&[u8], nom::error::ErrorKind)>> for Error {
         fn from(err: nom::Err<(&[u8], nom::error::ErrorKind)>) -> Self {
-            Self::Nmea {
-                msg: err.to_string(),
+            match err {
+                nom::Err::Incomplete(_) => Self::Incomplete,
+                _ => Self::Nmea {
+                    msg: err.to_string(),
+                },
             }
         }
     }
 
     impl<T: lib::std::fmt::Debug> From<nom::Err<nom::error::Error<T>>> for Error {
         fn from(err: nom::Err<nom::error::Error<T>>) -> Self {
-            Self::Nmea {
-                msg: format!("{:?}", err),
+            match err {
+                nom::Err::Incomplete(_) => Self::Incomplete,
+                _ => Self::Nmea {
+                    msg: format!("{:?}", err),
+                },
             }
         }
     }
@@ -81,9 +139,22 @@ mod err {
     #[derive(Copy, Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
     pub enum Error {
         //#[error("invalid NMEA sentence: '{msg}'")]
-        Nmea { msg: &'static str },
+        Nmea {
+            msg: &'static str,
+        },
         //#[error("checksum mismatch; expected: {expected:#X}, received: {found:#X}")]
-        Checksum { expected: u8, found: u8 },
+        Checksum {
+            expected: u8,
+            found: u8,
+        },
+        //#[error("incomplete input")]
+        Incomplete,
+        //#[error("message type {message_type} too short: {found} of {expected} bits")]
+        MessageTooShort {
+            message_type: u8,
+            expected: usize,
+            found: usize,
+        },
     }
 
     impl From<&'static str> for Error {
@@ -95,7 +166,7 @@ mod err {
     impl<T> From<nom::Err<T>> for Error {
         fn from(err: nom::Err<T>) -> Self {
             let err_str = match err {
-                nom::Err::Incomplete(_) => "Incomplete data",
+                nom::Err::Incomplete(_) => return Self::Incomplete,
                 nom::Err::Error(_) => "Parser error",
                 nom::Err::Failure(_) => "Parser unrecoverable failure",
             };
@@ -103,3 +174,18 @@ mod err {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AisParser;
+
+    #[test]
+    fn kind() {
+        let line = b"!AIVDM,1,1,,A,E>kb9I99S@0`8@:9ah;0TahI7@@;V4=v:nv;h00003vP100,0*8D";
+        let err = AisParser::new().parse(line, false).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Checksum);
+        assert_eq!(Error::from("Bad sentence").kind(), ErrorKind::Nmea);
+        assert_eq!(Error::Incomplete.kind(), ErrorKind::Incomplete);
+    }
+}
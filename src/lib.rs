@@ -11,7 +11,7 @@
 //! let line = b"!AIVDM,1,1,,B,E>kb9O9aS@7PUh10dh19@;0Tah2cWrfP:l?M`00003vP100,0*01";
 //!
 //! let mut parser = AisParser::new();
-//! if let AisFragments::Complete(sentence) = parser.parse(line, true)? {
+//! if let (_, AisFragments::Complete(sentence)) = parser.parse(line, true)? {
 //!     // This sentence is complete, ie unfragmented
 //!     assert_eq!(sentence.num_fragments, 1);
 //!     // The data was transmitted on AIS channel B
@@ -37,7 +37,7 @@
 pub mod lib {
     #[cfg(all(not(feature = "std"), not(feature = "alloc")))]
     pub mod std {
-        pub use core::{borrow, cmp, fmt, mem, result, str};
+        pub use core::{any, borrow, cmp, fmt, mem, result, str, time};
 
         pub mod vec {
             pub use heapless::Vec;
@@ -56,8 +56,8 @@ pub mod lib {
     #[cfg(all(not(feature = "std"), feature = "alloc"))]
     pub mod std {
         extern crate alloc;
-        pub use alloc::{borrow, fmt, format, str, string, vec};
-        pub use core::{cmp, mem, result};
+        pub use alloc::{borrow, boxed, collections, fmt, format, str, string, vec};
+        pub use core::{any, cmp, mem, result, time};
 
         pub trait Error: fmt::Debug + fmt::Display {
             fn source(&self) -> Option<&(dyn Error + 'static)> {
@@ -69,16 +69,25 @@ pub mod lib {
     #[cfg(feature = "std")]
     pub mod std {
         #[doc(hidden)]
-        pub use std::{borrow, cmp, error, fmt, format, io, mem, result, str, string, vec};
+        pub use std::{
+            any, borrow, boxed, cmp, collections, error, fmt, format, io, mem, result, str, string,
+            time, vec,
+        };
     }
 }
 
 pub mod errors;
 pub mod messages;
+#[cfg(feature = "profiling")]
+pub mod profiling;
 pub mod sentence;
+pub mod streaming;
 
 pub use errors::Result;
-pub use sentence::{AisFragments, AisParser};
+pub use messages::supported_message_types;
+#[cfg(feature = "std")]
+pub use sentence::{decode_file_to_vec, decode_reader};
+pub use sentence::{decode_lines, decode_with_metadata, AisFragments, AisParser};
 
 #[cfg(test)]
 mod test_helpers {
@@ -113,4 +122,33 @@ mod tests {
             parser.parse(line, true).unwrap();
         }
     }
+
+    #[test]
+    fn armor_round_trip() {
+        for line in TEST_MESSAGES.iter() {
+            let mut fields = line.split(|&byte| byte == b',').skip(5);
+            let payload = fields.next().unwrap();
+            let fill_bits = (fields.next().unwrap()[0] - b'0') as usize;
+            let unarmored = messages::unarmor(payload, fill_bits).unwrap();
+            let bit_len = payload.len() * 6 - fill_bits;
+            let (armored, armor_fill_bits) = messages::armor(&unarmored, bit_len).unwrap();
+            assert_eq!(&armored[..], payload);
+            assert_eq!(armor_fill_bits as usize, fill_bits);
+        }
+    }
+
+    #[test]
+    fn message_type_and_name() {
+        let mut parser = sentence::AisParser::new();
+        let mut messages = 0;
+        for line in TEST_MESSAGES.iter() {
+            if let AisFragments::Complete(sentence) = parser.parse(line, true).unwrap().1 {
+                let message = sentence.message.unwrap();
+                assert_eq!(message.message_type(), sentence.message_type);
+                assert!(!message.name().is_empty());
+                messages += 1;
+            }
+        }
+        assert_eq!(messages, 7);
+    }
 }
@@ -2,7 +2,7 @@
 use super::navigation::*;
 use super::parsers::*;
 use super::types::*;
-use super::AisMessageType;
+use super::{AisMessageType, CommonNavigation};
 use crate::errors::Result;
 use nom::bits::{bits, complete::take as take_bits};
 use nom::combinator::map;
@@ -107,6 +107,20 @@ pub struct AidToNavigationReport {
     pub assigned_mode: bool,
 }
 
+impl AidToNavigationReport {
+    /// Position of the reported point relative to the centre of the aid, as
+    /// `(forward, starboard)` in metres. See
+    /// [`position_reference_offset()`](../navigation/fn.position_reference_offset.html).
+    pub fn position_reference_offset(&self) -> Option<(f32, f32)> {
+        position_reference_offset(
+            self.dimension_to_bow,
+            self.dimension_to_stern,
+            self.dimension_to_port,
+            self.dimension_to_starboard,
+        )
+    }
+}
+
 impl<'a> AisMessageType<'a> for AidToNavigationReport {
     fn name(&self) -> &'static str {
         "Aid to Navigation Report"
@@ -118,6 +132,20 @@ impl<'a> AisMessageType<'a> for AidToNavigationReport {
     }
 }
 
+impl CommonNavigation for AidToNavigationReport {
+    fn mmsi(&self) -> u32 {
+        self.mmsi
+    }
+
+    fn longitude(&self) -> Option<f32> {
+        self.longitude
+    }
+
+    fn latitude(&self) -> Option<f32> {
+        self.latitude
+    }
+}
+
 fn parse_message(data: &[u8]) -> IResult<&[u8], AidToNavigationReport> {
     bits(move |data| -> IResult<_, _> {
         let (data, message_type) = take_bits(6u8)(data)?;
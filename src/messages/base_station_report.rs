@@ -3,7 +3,7 @@ use super::navigation::*;
 use super::parsers::*;
 use super::radio_status::{parse_radio, RadioStatus};
 use super::types::*;
-use super::AisMessageType;
+use super::{AisMessageType, CommonNavigation};
 use crate::errors::Result;
 use nom::bits::{bits, complete::take as take_bits};
 use nom::combinator::map;
@@ -24,6 +24,10 @@ pub struct BaseStationReport {
     pub longitude: Option<f32>,
     pub latitude: Option<f32>,
     pub epfd_type: Option<EpfdType>,
+    /// Transmission control for long-range broadcasts. When set, Class A stations
+    /// within coverage of this base station are requested to transmit long-range
+    /// broadcast messages (type 27); by default, they should not.
+    pub long_range_transmission: bool,
     pub raim: bool,
     pub radio_status: RadioStatus,
 }
@@ -39,6 +43,36 @@ impl<'a> AisMessageType<'a> for BaseStationReport {
     }
 }
 
+impl BaseStationReport {
+    /// Whether the station reports the time, but not its position, as some base
+    /// stations used only as a timing reference do. The date and time must all be
+    /// available; an hour of 24 means it is not.
+    pub fn is_timing_only(&self) -> bool {
+        self.longitude.is_none()
+            && self.latitude.is_none()
+            && self.year.is_some()
+            && self.month.is_some()
+            && self.day.is_some()
+            && self.hour < 24
+            && self.minute.is_some()
+            && self.second.is_some()
+    }
+}
+
+impl CommonNavigation for BaseStationReport {
+    fn mmsi(&self) -> u32 {
+        self.mmsi
+    }
+
+    fn longitude(&self) -> Option<f32> {
+        self.longitude
+    }
+
+    fn latitude(&self) -> Option<f32> {
+        self.latitude
+    }
+}
+
 fn parse_base(data: &[u8]) -> IResult<&[u8], BaseStationReport> {
     bits(move |data| -> IResult<_, _> {
         let (data, message_type) = take_bits(6u8)(data)?;
@@ -54,7 +88,8 @@ fn parse_base(data: &[u8]) -> IResult<&[u8], BaseStationReport> {
         let (data, longitude) = map(|data| signed_i32(data, 28), parse_longitude)(data)?;
         let (data, latitude) = map(|data| signed_i32(data, 27), parse_latitude)(data)?;
         let (data, epfd_type) = map(take_bits(4u8), EpfdType::parse)(data)?;
-        let (data, _spare) = take_bits::<_, u8, _, _>(10u8)(data)?;
+        let (data, long_range_transmission) = map(take_bits(1u8), u8_to_bool)(data)?;
+        let (data, _spare) = take_bits::<_, u16, _, _>(9u8)(data)?;
         let (data, raim) = map(take_bits(1u8), u8_to_bool)(data)?;
         let (data, radio_status) = parse_radio(data, message_type)?;
         Ok((
@@ -73,6 +108,7 @@ fn parse_base(data: &[u8]) -> IResult<&[u8], BaseStationReport> {
                 longitude,
                 latitude,
                 epfd_type,
+                long_range_transmission,
                 raim,
                 radio_status,
             },
@@ -85,6 +121,7 @@ mod tests {
     #![allow(clippy::zero_prefixed_literal)]
     #![allow(clippy::unreadable_literal)]
     use super::*;
+    use crate::messages::bit_writer::BitWriter;
     use crate::messages::radio_status::{SubMessage, SyncState};
     use crate::test_helpers::*;
 
@@ -106,6 +143,7 @@ mod tests {
         f32_equal_naive(message.longitude.unwrap(), -122.464775);
         f32_equal_naive(message.latitude.unwrap(), 37.794308);
         assert_eq!(message.epfd_type, None);
+        assert!(!message.long_range_transmission);
         assert!(message.raim);
         if let RadioStatus::Sotdma(radio_status) = message.radio_status {
             assert_eq!(radio_status.sync_state, SyncState::UtcDirect);
@@ -134,6 +172,7 @@ mod tests {
         assert_eq!(message.longitude, Some(-76.35236));
         assert_eq!(message.latitude, Some(36.883766));
         assert_eq!(message.epfd_type, Some(EpfdType::Surveyed));
+        assert!(!message.long_range_transmission);
         assert!(!message.raim);
         if let RadioStatus::Sotdma(radio_status) = message.radio_status {
             assert_eq!(radio_status.sync_state, SyncState::UtcDirect);
@@ -154,4 +193,54 @@ mod tests {
         assert_eq!(message.month, Some(15));
         assert_eq!(message.day, Some(31));
     }
+
+    #[test]
+    fn test_type4_long_range_transmission() {
+        let bytestream = b"403OtVAv7=i?;o?IaHE`4Iw020S:";
+        let mut bitstream = crate::messages::unarmor(bytestream, 0).unwrap();
+        // Transmission control flag follows the EPFD type, at bit 138
+        bitstream[138 / 8] |= 0x80 >> (138 % 8);
+        let message = BaseStationReport::parse(bitstream.as_ref()).unwrap();
+        assert!(message.long_range_transmission);
+        assert_eq!(message.epfd_type, None);
+        assert!(message.raim);
+    }
+
+    #[test]
+    fn test_type4_timing_only() {
+        let mut message = BitWriter::new();
+        message
+            .u32(4, 6)
+            .u32(0, 2)
+            .u32(3669145, 30)
+            .u32(2024, 14)
+            .u32(3, 4)
+            .u32(9, 5)
+            .u32(14, 5)
+            .u32(30, 6)
+            .u32(5, 6)
+            .u32(0, 1)
+            // Longitude 181 and latitude 91: position not available
+            .i32(108_600_000, 28)
+            .i32(54_600_000, 27)
+            .u32(0, 4)
+            .u32(0, 1)
+            .u32(0, 9)
+            .u32(0, 1)
+            .u32(0, 19);
+        let message = BaseStationReport::parse(message.as_bytes()).unwrap();
+        assert_eq!(message.longitude, None);
+        assert_eq!(message.latitude, None);
+        assert_eq!(message.year, Some(2024));
+        assert_eq!(message.month, Some(3));
+        assert_eq!(message.day, Some(9));
+        assert_eq!(message.hour, 14);
+        assert_eq!(message.minute, Some(30));
+        assert_eq!(message.second, Some(5));
+        assert!(message.is_timing_only());
+
+        let bitstream = crate::messages::unarmor(b"403OtVAv7=i?;o?IaHE`4Iw020S:", 0).unwrap();
+        let message = BaseStationReport::parse(bitstream.as_ref()).unwrap();
+        assert!(!message.is_timing_only());
+    }
 }
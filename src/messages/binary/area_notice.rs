@@ -0,0 +1,455 @@
+//! Area notice (IMO SN.1/Circ.289, DAC 1, FID 22 broadcast or FID 23 addressed)
+use super::super::parsers::*;
+use super::super::push_unwrap;
+use super::{parse_hour_24, parse_latitude_min_1000, parse_longitude_min_1000};
+use crate::errors::Result;
+use crate::lib;
+use nom::bits::{bits, complete::take as take_bits};
+use nom::combinator::map;
+use nom::IResult;
+
+/// Maximum number of sub-areas in a single message
+const MAX_SUB_AREAS: usize = 10;
+/// Maximum number of points in a polyline or polygon sub-area
+const MAX_POINTS: usize = 4;
+/// Width of a sub-area, in bits
+const SUB_AREA_BITS: usize = 87;
+
+/// Position of a circle, rectangle, or sector
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct AreaPosition {
+    pub longitude: Option<f32>,
+    pub latitude: Option<f32>,
+    /// Number of decimal places of the position that are significant, in
+    /// minutes, from 0 to 4
+    pub precision: Option<u8>,
+}
+
+/// Point of a polyline or polygon, relative to the previous point. The first
+/// point is relative to the position of the sub-area before the polyline or
+/// polygon.
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct RelativePoint {
+    /// Bearing from the previous point, in degrees
+    pub bearing: f32,
+    /// Distance from the previous point, in metres
+    pub distance: u32,
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub type PointList = lib::std::vec::Vec<RelativePoint>;
+#[cfg(all(not(feature = "std"), not(feature = "alloc")))]
+pub type PointList = lib::std::vec::Vec<RelativePoint, MAX_POINTS>;
+
+/// Part of the area covered by a notice. Distances are in metres, already
+/// multiplied by the scale factor sent with each sub-area.
+#[derive(Debug, PartialEq)]
+pub enum SubArea {
+    /// Circle, or a point if `radius` is 0
+    Circle {
+        center: AreaPosition,
+        radius: u32,
+    },
+    /// Rectangle extending east and north from its south-west corner, then
+    /// rotated clockwise about it by `orientation` degrees
+    Rectangle {
+        corner: AreaPosition,
+        east: u32,
+        north: u32,
+        orientation: Option<u16>,
+    },
+    /// Part of a circle, clockwise from the left bearing to the right one
+    Sector {
+        center: AreaPosition,
+        radius: u32,
+        left_bound: Option<u16>,
+        right_bound: Option<u16>,
+    },
+    /// Line through up to four points. Longer lines continue over several
+    /// consecutive `Polyline` sub-areas.
+    Polyline(PointList),
+    /// Closed shape through up to four points, continuing over consecutive
+    /// `Polygon` sub-areas in the same way as `Polyline`
+    Polygon(PointList),
+    /// Text associated with the area. Longer text continues over consecutive
+    /// `Text` sub-areas.
+    Text(AsciiString),
+    Reserved(u8),
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub type SubAreaList = lib::std::vec::Vec<SubArea>;
+#[cfg(all(not(feature = "std"), not(feature = "alloc")))]
+pub type SubAreaList = lib::std::vec::Vec<SubArea, MAX_SUB_AREAS>;
+
+/// Notice about an area, such as a caution area, restricted area, or route
+/// recommendation, made up of one or more shapes
+#[derive(Debug, PartialEq)]
+pub struct AreaNotice {
+    /// Links this message to related messages
+    pub linkage_id: u16,
+    /// Kind of notice, as defined in table 11.10 of IMO SN.1/Circ.289; for
+    /// example, 0 is a caution area with marine mammals habitat
+    pub notice_type: Option<u8>,
+    /// UTC month of the start of the notice
+    pub start_month: Option<u8>,
+    pub start_day: Option<u8>,
+    pub start_hour: Option<u8>,
+    pub start_minute: Option<u8>,
+    /// Minutes the notice is valid for, from the start time. `None` means the
+    /// validity isn't limited.
+    pub duration: Option<u32>,
+    pub sub_areas: SubAreaList,
+}
+
+impl AreaNotice {
+    pub fn parse(data: &[u8]) -> Result<Self> {
+        let (_, report) = parse_message(data)?;
+        Ok(report)
+    }
+}
+
+/// Notice type 127 means undefined
+fn parse_notice_type(data: u8) -> Option<u8> {
+    match data {
+        127 => None,
+        _ => Some(data),
+    }
+}
+
+fn parse_duration(data: u32) -> Option<u32> {
+    match data {
+        262_143 => None,
+        _ => Some(data),
+    }
+}
+
+fn parse_bearing(data: u16) -> Option<u16> {
+    (data < 360).then_some(data)
+}
+
+/// Scale factor for distances, from 1 to 1000
+fn parse_scale(data: (&[u8], usize)) -> IResult<(&[u8], usize), u32> {
+    map(take_bits(2u8), |exponent: u8| 10u32.pow(exponent as u32))(data)
+}
+
+fn parse_position(data: (&[u8], usize)) -> IResult<(&[u8], usize), AreaPosition> {
+    let (data, longitude) = map(|data| signed_i32(data, 25), parse_longitude_min_1000)(data)?;
+    let (data, latitude) = map(|data| signed_i32(data, 24), parse_latitude_min_1000)(data)?;
+    let (data, precision) = map(take_bits(3u8), |precision| {
+        (precision <= 4).then_some(precision)
+    })(data)?;
+    Ok((
+        data,
+        AreaPosition {
+            longitude,
+            latitude,
+            precision,
+        },
+    ))
+}
+
+/// Up to four points, each a bearing in half degrees and a distance. Points
+/// that aren't available mark the end of the line.
+fn parse_points(data: (&[u8], usize)) -> IResult<(&[u8], usize), PointList> {
+    let (mut data, scale) = parse_scale(data)?;
+    let mut points = PointList::default();
+    let mut ended = false;
+    for _ in 0..MAX_POINTS {
+        let (remaining, bearing) = take_bits::<_, u16, _, _>(10u8)(data)?;
+        let (remaining, distance) = take_bits::<_, u32, _, _>(10u8)(remaining)?;
+        data = remaining;
+        ended |= bearing >= 720 || distance == 1023;
+        if !ended {
+            push_unwrap(
+                &mut points,
+                RelativePoint {
+                    bearing: bearing as f32 / 2.0,
+                    distance: distance * scale,
+                },
+            );
+        }
+    }
+    let (data, _spare) = take_bits::<_, u8, _, _>(2u8)(data)?;
+    Ok((data, points))
+}
+
+fn parse_sub_area(data: (&[u8], usize)) -> IResult<(&[u8], usize), SubArea> {
+    let (data, shape) = take_bits(3u8)(data)?;
+    match shape {
+        0 => {
+            let (data, scale) = parse_scale(data)?;
+            let (data, center) = parse_position(data)?;
+            let (data, radius) = take_bits::<_, u32, _, _>(12u8)(data)?;
+            let (data, _spare) = take_bits::<_, u32, _, _>(18u8)(data)?;
+            Ok((
+                data,
+                SubArea::Circle {
+                    center,
+                    radius: radius * scale,
+                },
+            ))
+        }
+        1 => {
+            let (data, scale) = parse_scale(data)?;
+            let (data, corner) = parse_position(data)?;
+            let (data, east) = take_bits::<_, u32, _, _>(8u8)(data)?;
+            let (data, north) = take_bits::<_, u32, _, _>(8u8)(data)?;
+            let (data, orientation) = map(take_bits(9u16), parse_bearing)(data)?;
+            let (data, _spare) = take_bits::<_, u8, _, _>(5u8)(data)?;
+            Ok((
+                data,
+                SubArea::Rectangle {
+                    corner,
+                    east: east * scale,
+                    north: north * scale,
+                    orientation,
+                },
+            ))
+        }
+        2 => {
+            let (data, scale) = parse_scale(data)?;
+            let (data, center) = parse_position(data)?;
+            let (data, radius) = take_bits::<_, u32, _, _>(12u8)(data)?;
+            let (data, left_bound) = map(take_bits(9u16), parse_bearing)(data)?;
+            let (data, right_bound) = map(take_bits(9u16), parse_bearing)(data)?;
+            Ok((
+                data,
+                SubArea::Sector {
+                    center,
+                    radius: radius * scale,
+                    left_bound,
+                    right_bound,
+                },
+            ))
+        }
+        3 => map(parse_points, SubArea::Polyline)(data),
+        4 => map(parse_points, SubArea::Polygon)(data),
+        5 => map(|data| parse_6bit_ascii(data, 84), SubArea::Text)(data),
+        _ => {
+            let (data, _reserved) = take_bits::<_, u128, _, _>(84u8)(data)?;
+            Ok((data, SubArea::Reserved(shape)))
+        }
+    }
+}
+
+fn parse_message(data: &[u8]) -> IResult<&[u8], AreaNotice> {
+    bits(move |data| -> IResult<_, _> {
+        let (data, linkage_id) = take_bits(10u16)(data)?;
+        let (data, notice_type) = map(take_bits(7u8), parse_notice_type)(data)?;
+        let (data, start_month) = parse_month(data)?;
+        let (data, start_day) = parse_day(data)?;
+        let (data, start_hour) = map(parse_hour, parse_hour_24)(data)?;
+        let (data, start_minute) = parse_minsec(data)?;
+        let (mut data, duration) = map(take_bits(18u32), parse_duration)(data)?;
+        let mut sub_areas = SubAreaList::default();
+        while remaining_bits(data) >= SUB_AREA_BITS && sub_areas.len() < MAX_SUB_AREAS {
+            let (remaining, sub_area) = parse_sub_area(data)?;
+            push_unwrap(&mut sub_areas, sub_area);
+            data = remaining;
+        }
+        Ok((
+            data,
+            AreaNotice {
+                linkage_id,
+                notice_type,
+                start_month,
+                start_day,
+                start_hour,
+                start_minute,
+                duration,
+                sub_areas,
+            },
+        ))
+    })(data)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unreadable_literal)]
+    use super::*;
+    use crate::messages::binary::BinaryPayload;
+    use crate::messages::binary_broadcast_message::BinaryBroadcastMessage;
+    use crate::messages::bit_writer::BitWriter;
+    use crate::messages::AisMessageType;
+    use crate::test_helpers::*;
+
+    fn notice_header(message: &mut BitWriter) {
+        message
+            .u32(8, 6)
+            .u32(0, 2)
+            .u32(366999712, 30)
+            .u32(0, 2)
+            .u32(1, 10)
+            .u32(22, 6);
+        message
+            .u32(105, 10)
+            .u32(1, 7)
+            .u32(6, 4)
+            .u32(2, 5)
+            .u32(13, 5)
+            .u32(45, 6)
+            .u32(1440, 18);
+    }
+
+    #[test]
+    fn test_sample_point() {
+        // Published test sentence for the IMO SN.1/Circ.289 area notice:
+        // !AIVDM,1,1,,A,81mg=5@0EP:0>H0007P>0<D1<qp400000,0*1D
+        let bitstream = crate::messages::unarmor(b"81mg=5@0EP:0>H0007P>0<D1<qp400000", 0).unwrap();
+        let report = BinaryBroadcastMessage::parse(bitstream.as_ref()).unwrap();
+        assert_eq!(report.mmsi, 123456789);
+        let notice = match report.decode_payload() {
+            BinaryPayload::AreaNotice(notice) => notice,
+            other => panic!("Expected area notice, got {:?}", other),
+        };
+        assert_eq!(notice.linkage_id, 10);
+        assert_eq!(notice.notice_type, Some(0));
+        assert_eq!(notice.start_month, Some(7));
+        assert_eq!(notice.start_day, Some(6));
+        assert_eq!(notice.start_hour, Some(0));
+        assert_eq!(notice.start_minute, Some(0));
+        assert_eq!(notice.duration, Some(60));
+        assert_eq!(notice.sub_areas.len(), 1);
+        match &notice.sub_areas[0] {
+            SubArea::Circle { center, radius } => {
+                f32_equal_naive(center.longitude.unwrap(), -69.8);
+                f32_equal_naive(center.latitude.unwrap(), 42.0);
+                assert_eq!(center.precision, Some(4));
+                // A point: scale factor 0 (a factor of 1), and a radius of 0
+                assert_eq!(*radius, 0);
+            }
+            other => panic!("Expected a circle, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_circle_and_text() {
+        let mut message = BitWriter::new();
+        notice_header(&mut message);
+        message
+            .u32(0, 3)
+            .u32(2, 2)
+            .i32(-7_350_000, 25)
+            .i32(2_280_000, 24)
+            .u32(3, 3)
+            .u32(12, 12)
+            .u32(0, 18);
+        message.u32(5, 3).string6("WHALES SEEN", 84);
+        let report = BinaryBroadcastMessage::parse(message.as_bytes()).unwrap();
+        let notice = match report.decode_payload() {
+            BinaryPayload::AreaNotice(notice) => notice,
+            other => panic!("Expected area notice, got {:?}", other),
+        };
+        assert_eq!(notice.linkage_id, 105);
+        assert_eq!(notice.notice_type, Some(1));
+        assert_eq!(notice.start_month, Some(6));
+        assert_eq!(notice.start_day, Some(2));
+        assert_eq!(notice.start_hour, Some(13));
+        assert_eq!(notice.start_minute, Some(45));
+        assert_eq!(notice.duration, Some(1440));
+        assert_eq!(notice.sub_areas.len(), 2);
+        match &notice.sub_areas[0] {
+            SubArea::Circle { center, radius } => {
+                f32_equal_naive(center.longitude.unwrap(), -122.5);
+                f32_equal_naive(center.latitude.unwrap(), 38.0);
+                assert_eq!(center.precision, Some(3));
+                // Scale factor 2 means a factor of 100
+                assert_eq!(*radius, 1200);
+            }
+            other => panic!("Expected a circle, got {:?}", other),
+        }
+        assert_eq!(notice.sub_areas[1], SubArea::Text("WHALES SEEN".into()));
+    }
+
+    #[test]
+    fn test_rectangle_sector_and_polygon() {
+        let mut message = BitWriter::new();
+        message
+            .u32(0, 10)
+            .u32(127, 7)
+            .u32(0, 4)
+            .u32(0, 5)
+            .u32(24, 5)
+            .u32(60, 6)
+            .u32(262143, 18);
+        message
+            .u32(1, 3)
+            .u32(1, 2)
+            .i32(10_860_000, 25)
+            .i32(5_460_000, 24)
+            .u32(7, 3)
+            .u32(25, 8)
+            .u32(40, 8)
+            .u32(45, 9)
+            .u32(0, 5);
+        message
+            .u32(2, 3)
+            .u32(0, 2)
+            .i32(600_000, 25)
+            .i32(-600_000, 24)
+            .u32(4, 3)
+            .u32(500, 12)
+            .u32(350, 9)
+            .u32(360, 9);
+        message
+            .u32(4, 3)
+            .u32(3, 2)
+            .u32(181, 10)
+            .u32(2, 10)
+            .u32(0, 10)
+            .u32(5, 10)
+            .u32(720, 10)
+            .u32(0, 10)
+            .u32(0, 10)
+            .u32(1, 10)
+            .u32(0, 2);
+        let notice = AreaNotice::parse(message.as_bytes()).unwrap();
+        assert_eq!(notice.notice_type, None);
+        assert_eq!(notice.start_hour, None);
+        assert_eq!(notice.duration, None);
+        assert_eq!(
+            notice.sub_areas[0],
+            SubArea::Rectangle {
+                corner: AreaPosition {
+                    longitude: None,
+                    latitude: None,
+                    precision: None,
+                },
+                east: 250,
+                north: 400,
+                orientation: Some(45),
+            }
+        );
+        assert_eq!(
+            notice.sub_areas[1],
+            SubArea::Sector {
+                center: AreaPosition {
+                    longitude: Some(10.0),
+                    latitude: Some(-10.0),
+                    precision: Some(4),
+                },
+                radius: 500,
+                left_bound: Some(350),
+                right_bound: None,
+            }
+        );
+        match &notice.sub_areas[2] {
+            // Points after the first unavailable one are ignored
+            SubArea::Polygon(points) => assert_eq!(
+                &points[..],
+                [
+                    RelativePoint {
+                        bearing: 90.5,
+                        distance: 2000,
+                    },
+                    RelativePoint {
+                        bearing: 0.0,
+                        distance: 5000,
+                    },
+                ]
+            ),
+            other => panic!("Expected a polygon, got {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,136 @@
+//! Clearance time to enter port (IMO SN.1/Circ.289, DAC 1, FID 18)
+use super::super::parsers::*;
+use super::{parse_hour_24, parse_latitude_min_1000, parse_longitude_min_1000};
+use crate::errors::Result;
+use nom::bits::{bits, complete::take as take_bits};
+use nom::combinator::map;
+use nom::IResult;
+
+/// Time at which a vessel is cleared to enter port, sent addressed (type 6)
+#[derive(Debug, PartialEq)]
+pub struct ClearanceTimeToEnterPort {
+    /// Links this message to an earlier request or to related messages
+    pub linkage_id: u16,
+    pub month: Option<u8>,
+    pub day: Option<u8>,
+    pub hour: Option<u8>,
+    pub minute: Option<u8>,
+    /// Name of the port and berth
+    pub port_berth: AsciiString,
+    /// UN/LOCODE of the destination
+    pub destination: AsciiString,
+    /// Longitude of the berth
+    pub longitude: Option<f32>,
+    /// Latitude of the berth
+    pub latitude: Option<f32>,
+}
+
+impl ClearanceTimeToEnterPort {
+    pub fn parse(data: &[u8]) -> Result<Self> {
+        let (_, report) = parse_message(data)?;
+        Ok(report)
+    }
+}
+
+fn parse_message(data: &[u8]) -> IResult<&[u8], ClearanceTimeToEnterPort> {
+    bits(move |data| -> IResult<_, _> {
+        let (data, linkage_id) = take_bits(10u16)(data)?;
+        let (data, month) = parse_month(data)?;
+        let (data, day) = parse_day(data)?;
+        let (data, hour) = map(parse_hour, parse_hour_24)(data)?;
+        let (data, minute) = parse_minsec(data)?;
+        let (data, port_berth) = parse_6bit_ascii(data, 120)?;
+        let (data, destination) = parse_6bit_ascii(data, 30)?;
+        let (data, longitude) = map(|data| signed_i32(data, 25), parse_longitude_min_1000)(data)?;
+        let (data, latitude) = map(|data| signed_i32(data, 24), parse_latitude_min_1000)(data)?;
+        Ok((
+            data,
+            ClearanceTimeToEnterPort {
+                linkage_id,
+                month,
+                day,
+                hour,
+                minute,
+                port_berth,
+                destination,
+                longitude,
+                latitude,
+            },
+        ))
+    })(data)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unreadable_literal)]
+    use super::*;
+    use crate::messages::binary::BinaryPayload;
+    use crate::messages::binary_addressed::BinaryAddressedMessage;
+    use crate::messages::bit_writer::BitWriter;
+    use crate::messages::AisMessageType;
+    use crate::test_helpers::*;
+
+    #[test]
+    fn test_clearance_time() {
+        let mut message = BitWriter::new();
+        message
+            .u32(6, 6)
+            .u32(0, 2)
+            .u32(2320123, 30)
+            .u32(0, 2)
+            .u32(235009802, 30)
+            .bool(false)
+            .bool(false)
+            .u32(1, 10)
+            .u32(18, 6);
+        message
+            .u32(17, 10)
+            .u32(3, 4)
+            .u32(14, 5)
+            .u32(9, 5)
+            .u32(30, 6)
+            .string6("SOUTHAMPTON BERTH 38", 120)
+            .string6("GBSOU", 30)
+            .i32(-84_300, 25)
+            .i32(3_054_000, 24)
+            .u32(0, 32)
+            .u32(0, 11);
+        let report = BinaryAddressedMessage::parse(message.as_bytes()).unwrap();
+        let clearance = match report.decode_payload() {
+            BinaryPayload::ClearanceTimeToEnterPort(clearance) => clearance,
+            other => panic!("Expected clearance time, got {:?}", other),
+        };
+        assert_eq!(clearance.linkage_id, 17);
+        assert_eq!(clearance.month, Some(3));
+        assert_eq!(clearance.day, Some(14));
+        assert_eq!(clearance.hour, Some(9));
+        assert_eq!(clearance.minute, Some(30));
+        assert_eq!(clearance.port_berth, "SOUTHAMPTON BERTH 38");
+        assert_eq!(clearance.destination, "GBSOU");
+        f32_equal_naive(clearance.longitude.unwrap(), -1.405);
+        f32_equal_naive(clearance.latitude.unwrap(), 50.9);
+    }
+
+    #[test]
+    fn test_clearance_time_not_available() {
+        let mut message = BitWriter::new();
+        message
+            .u32(0, 10)
+            .u32(0, 4)
+            .u32(0, 5)
+            .u32(24, 5)
+            .u32(60, 6)
+            .string6("", 120)
+            .string6("", 30)
+            .i32(10_860_000, 25)
+            .i32(5_460_000, 24);
+        let clearance = ClearanceTimeToEnterPort::parse(message.as_bytes()).unwrap();
+        assert_eq!(clearance.month, None);
+        assert_eq!(clearance.day, None);
+        assert_eq!(clearance.hour, None);
+        assert_eq!(clearance.minute, None);
+        assert_eq!(clearance.port_berth, "");
+        assert_eq!(clearance.longitude, None);
+        assert_eq!(clearance.latitude, None);
+    }
+}
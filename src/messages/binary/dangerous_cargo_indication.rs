@@ -0,0 +1,168 @@
+//! Dangerous cargo indication (IMO SN.1/Circ.289, DAC 1, FID 25)
+use super::super::parsers::*;
+use super::super::push_unwrap;
+use crate::errors::Result;
+use crate::lib;
+use nom::bits::{bits, complete::take as take_bits};
+use nom::combinator::map;
+use nom::IResult;
+
+/// Maximum number of cargo entries in a single message
+const MAX_CARGOS: usize = 28;
+
+/// Unit of the total amount of dangerous cargo
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum QuantityUnit {
+    Kilograms,
+    Tonnes,
+    Kilotonnes,
+}
+
+impl QuantityUnit {
+    pub fn parse(data: u8) -> Option<Self> {
+        match data {
+            0 => None,
+            1 => Some(Self::Kilograms),
+            2 => Some(Self::Tonnes),
+            3 => Some(Self::Kilotonnes),
+            _ => unreachable!(),
+        }
+    }
+}
+
+/// A kind of dangerous cargo on board, identified by one of several coding schemes
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum DangerousCargo {
+    /// IMDG Code class or division
+    ImdgClass(u8),
+    /// UN number
+    UnNumber(u16),
+    /// IMSBC (formerly BC) Code hazard group
+    BcCode(u8),
+    /// MARPOL Annex I oil category
+    MarpolAnnexI(u8),
+    /// MARPOL Annex II (IBC Code) pollution category
+    MarpolAnnexII(u8),
+    /// Code for regional use
+    Regional(u16),
+    /// Unavailable or reserved code type, with its raw 13 bit value
+    Unknown { code_type: u8, value: u16 },
+}
+
+impl DangerousCargo {
+    pub fn parse(data: (&[u8], usize)) -> IResult<(&[u8], usize), Self> {
+        let (data, code_type) = take_bits(4u8)(data)?;
+        let (data, value) = take_bits::<_, u16, _, _>(13u8)(data)?;
+        // Shorter codes are left-aligned, followed by spare bits
+        let cargo = match code_type {
+            1 => Self::ImdgClass((value >> 6) as u8),
+            2 => Self::UnNumber(value),
+            3 => Self::BcCode((value >> 10) as u8),
+            4 => Self::MarpolAnnexI((value >> 9) as u8),
+            5 => Self::MarpolAnnexII((value >> 10) as u8),
+            6 => Self::Regional(value),
+            _ => Self::Unknown { code_type, value },
+        };
+        Ok((data, cargo))
+    }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub type CargoList = lib::std::vec::Vec<DangerousCargo>;
+#[cfg(all(not(feature = "std"), not(feature = "alloc")))]
+pub type CargoList = lib::std::vec::Vec<DangerousCargo, MAX_CARGOS>;
+
+/// Dangerous cargo carried by a vessel, sent addressed (type 6)
+#[derive(Debug, PartialEq)]
+pub struct DangerousCargoIndication {
+    pub unit: Option<QuantityUnit>,
+    /// Total amount of dangerous cargo, in `unit`s
+    pub amount: u16,
+    pub cargos: CargoList,
+}
+
+impl DangerousCargoIndication {
+    pub fn parse(data: &[u8]) -> Result<Self> {
+        let (_, report) = parse_message(data)?;
+        Ok(report)
+    }
+}
+
+fn parse_message(data: &[u8]) -> IResult<&[u8], DangerousCargoIndication> {
+    bits(move |data| -> IResult<_, _> {
+        let (data, unit) = map(take_bits(2u8), QuantityUnit::parse)(data)?;
+        let (mut data, amount) = take_bits(10u16)(data)?;
+        let mut cargos = CargoList::default();
+        while remaining_bits(data) >= 17 && cargos.len() < MAX_CARGOS {
+            let (remaining, cargo) = DangerousCargo::parse(data)?;
+            push_unwrap(&mut cargos, cargo);
+            data = remaining;
+        }
+        Ok((
+            data,
+            DangerousCargoIndication {
+                unit,
+                amount,
+                cargos,
+            },
+        ))
+    })(data)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unreadable_literal)]
+    use super::*;
+    use crate::messages::binary::BinaryPayload;
+    use crate::messages::binary_addressed::BinaryAddressedMessage;
+    use crate::messages::bit_writer::BitWriter;
+    use crate::messages::AisMessageType;
+
+    #[test]
+    fn test_dangerous_cargo() {
+        let mut message = BitWriter::new();
+        message
+            .u32(6, 6)
+            .u32(0, 2)
+            .u32(244123456, 30)
+            .u32(1, 2)
+            .u32(2442001, 30)
+            .bool(false)
+            .bool(false)
+            .u32(1, 10)
+            .u32(25, 6);
+        message
+            .u32(2, 2)
+            .u32(340, 10)
+            .u32(2, 4)
+            .u32(1203, 13)
+            .u32(1, 4)
+            .u32(3 << 6, 13)
+            .u32(4, 4)
+            .u32(2 << 9, 13);
+        let report = BinaryAddressedMessage::parse(message.as_bytes()).unwrap();
+        let indication = match report.decode_payload() {
+            BinaryPayload::DangerousCargoIndication(indication) => indication,
+            other => panic!("Expected dangerous cargo indication, got {:?}", other),
+        };
+        assert_eq!(indication.unit, Some(QuantityUnit::Tonnes));
+        assert_eq!(indication.amount, 340);
+        assert_eq!(
+            &indication.cargos[..],
+            [
+                DangerousCargo::UnNumber(1203),
+                DangerousCargo::ImdgClass(3),
+                DangerousCargo::MarpolAnnexI(2),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_no_cargo() {
+        let mut message = BitWriter::new();
+        message.u32(0, 2).u32(0, 10);
+        let indication = DangerousCargoIndication::parse(message.as_bytes()).unwrap();
+        assert_eq!(indication.unit, None);
+        assert!(indication.cargos.is_empty());
+    }
+}
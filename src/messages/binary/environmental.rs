@@ -0,0 +1,538 @@
+//! Environmental sensor reports (IMO SN.1/Circ.289, DAC 1, FID 26)
+use super::super::parsers::*;
+use super::super::push_unwrap;
+use super::meteo_hydro::{
+    parse_current, parse_direction, parse_waves, signed_tenths, tenths, up_to, Current, Tendency,
+    Waves,
+};
+use super::{parse_hour_24, parse_latitude_min_1000, parse_longitude_min_1000};
+use crate::errors::Result;
+use crate::lib;
+use nom::bits::{bits, complete::take as take_bits};
+use nom::combinator::map;
+use nom::IResult;
+
+/// Maximum number of sensor reports in a single message
+const MAX_SENSOR_REPORTS: usize = 8;
+/// Width of a sensor report, in bits
+const SENSOR_REPORT_BITS: usize = 112;
+
+/// How the data from a sensor was obtained
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum SensorDataDescription {
+    NoData,
+    RawRealTime,
+    RealTimeWithQualityControl,
+    Predicted,
+    Forecast,
+    Nowcast,
+    Reserved(u8),
+}
+
+impl SensorDataDescription {
+    pub fn parse(data: u8) -> Option<Self> {
+        match data {
+            0 => Some(Self::NoData),
+            1 => Some(Self::RawRealTime),
+            2 => Some(Self::RealTimeWithQualityControl),
+            3 => Some(Self::Predicted),
+            4 => Some(Self::Forecast),
+            5 => Some(Self::Nowcast),
+            6 => Some(Self::Reserved(data)),
+            7 => None,
+            _ => unreachable!(),
+        }
+    }
+}
+
+/// Position of the sensor site
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct SiteLocation {
+    pub longitude: Option<f32>,
+    pub latitude: Option<f32>,
+    /// Number of decimal places of the position that are significant
+    pub precision: u8,
+    /// Altitude of the sensor in metres; 200.1 means more than 200 m
+    pub altitude: Option<f32>,
+    /// Type of organization owning the sensor
+    pub owner: u8,
+    /// Minutes after which the site's data should be considered stale
+    pub data_timeout: Option<u16>,
+}
+
+/// Wind observation, with an optional forecast
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct WindReport {
+    /// Average wind speed in knots; 122 means 122 knots or more
+    pub wind_speed: Option<u8>,
+    pub wind_gust: Option<u8>,
+    /// Wind direction in degrees
+    pub wind_direction: Option<u16>,
+    pub wind_gust_direction: Option<u16>,
+    pub description: Option<SensorDataDescription>,
+    pub forecast_wind_speed: Option<u8>,
+    pub forecast_wind_gust: Option<u8>,
+    pub forecast_wind_direction: Option<u16>,
+    /// UTC time the forecast is valid from
+    pub forecast_day: Option<u8>,
+    pub forecast_hour: Option<u8>,
+    pub forecast_minute: Option<u8>,
+    /// Minutes the forecast is valid for
+    pub forecast_duration: Option<u8>,
+}
+
+/// Water level observation, with an optional forecast
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct WaterLevelReport {
+    /// Set if the level is the water depth, rather than relative to the datum
+    pub is_depth: bool,
+    /// Water level in metres
+    pub water_level: Option<f32>,
+    pub trend: Option<Tendency>,
+    /// Vertical reference datum the level is relative to
+    pub vertical_datum: u8,
+    pub description: Option<SensorDataDescription>,
+    pub forecast_is_depth: bool,
+    pub forecast_water_level: Option<f32>,
+    /// UTC time the forecast is valid from
+    pub forecast_day: Option<u8>,
+    pub forecast_hour: Option<u8>,
+    pub forecast_minute: Option<u8>,
+    /// Minutes the forecast is valid for
+    pub forecast_duration: Option<u8>,
+}
+
+/// Sea state observation
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct SeaStateReport {
+    pub swell: Waves,
+    /// Sea state on the Beaufort scale
+    pub sea_state: Option<u8>,
+    pub swell_description: Option<SensorDataDescription>,
+    /// Water temperature in degrees Celsius
+    pub water_temperature: Option<f32>,
+    /// Depth of the water temperature measurement in metres
+    pub water_temperature_depth: Option<f32>,
+    pub water_temperature_description: Option<SensorDataDescription>,
+    pub waves: Waves,
+    pub waves_description: Option<SensorDataDescription>,
+    /// Salinity in parts per thousand
+    pub salinity: Option<f32>,
+}
+
+/// Data carried by a sensor report, depending on its type
+#[derive(Debug, PartialEq, Clone)]
+pub enum SensorData {
+    SiteLocation(SiteLocation),
+    /// Name of the sensor site
+    StationId(AsciiString),
+    Wind(WindReport),
+    WaterLevel(WaterLevelReport),
+    /// Horizontal current at up to three depths
+    CurrentFlow2d {
+        currents: [Current; 3],
+        description: Option<SensorDataDescription>,
+    },
+    SeaState(SeaStateReport),
+    /// Report types that aren't decoded; `data` holds the 85 bits following the
+    /// report header
+    Raw {
+        report_type: u8,
+        data: u128,
+    },
+}
+
+/// Reading from one sensor at a site
+#[derive(Debug, PartialEq, Clone)]
+pub struct SensorReport {
+    /// UTC time of the reading
+    pub day: Option<u8>,
+    pub hour: Option<u8>,
+    pub minute: Option<u8>,
+    /// Identifies the site, so reports from the same site can be linked
+    pub site_id: u8,
+    pub data: SensorData,
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub type SensorReportList = lib::std::vec::Vec<SensorReport>;
+#[cfg(all(not(feature = "std"), not(feature = "alloc")))]
+pub type SensorReportList = lib::std::vec::Vec<SensorReport, MAX_SENSOR_REPORTS>;
+
+/// Parses the sensor reports broadcast (type 8) by an environmental station
+pub fn parse_sensor_reports(data: &[u8]) -> Result<SensorReportList> {
+    let (_, reports) = parse_message(data)?;
+    Ok(reports)
+}
+
+fn parse_data_timeout(data: u8) -> Option<u16> {
+    match data {
+        1 => Some(10),
+        2 => Some(60),
+        3 => Some(6 * 60),
+        4 => Some(12 * 60),
+        5 => Some(24 * 60),
+        _ => None,
+    }
+}
+
+/// Water level in hundredths of a metre, with -32768 meaning not available
+fn parse_water_level(data: i32) -> Option<f32> {
+    (data != -32768).then(|| data as f32 / 100.0)
+}
+
+fn parse_speed_knots(data: u8) -> Option<u8> {
+    up_to(data, 122)
+}
+
+fn parse_duration(data: u8) -> Option<u8> {
+    match data {
+        255 => None,
+        _ => Some(data),
+    }
+}
+
+fn parse_description(
+    data: (&[u8], usize),
+) -> IResult<(&[u8], usize), Option<SensorDataDescription>> {
+    map(take_bits(3u8), SensorDataDescription::parse)(data)
+}
+
+fn parse_site_location(data: (&[u8], usize)) -> IResult<(&[u8], usize), SensorData> {
+    let (data, longitude) = map(|data| signed_i32(data, 25), parse_longitude_min_1000)(data)?;
+    let (data, latitude) = map(|data| signed_i32(data, 24), parse_latitude_min_1000)(data)?;
+    let (data, precision) = take_bits(3u8)(data)?;
+    let (data, altitude) = map(take_bits(12u16), |altitude| tenths(altitude, 2001, 0))(data)?;
+    let (data, owner) = take_bits(4u8)(data)?;
+    let (data, data_timeout) = map(take_bits(3u8), parse_data_timeout)(data)?;
+    let (data, _spare) = take_bits::<_, u16, _, _>(14u8)(data)?;
+    Ok((
+        data,
+        SensorData::SiteLocation(SiteLocation {
+            longitude,
+            latitude,
+            precision,
+            altitude,
+            owner,
+            data_timeout,
+        }),
+    ))
+}
+
+fn parse_station_id(data: (&[u8], usize)) -> IResult<(&[u8], usize), SensorData> {
+    let (data, name) = parse_6bit_ascii(data, 84)?;
+    let (data, _spare) = take_bits::<_, u8, _, _>(1u8)(data)?;
+    Ok((data, SensorData::StationId(name)))
+}
+
+fn parse_wind(data: (&[u8], usize)) -> IResult<(&[u8], usize), SensorData> {
+    let (data, wind_speed) = map(take_bits(7u8), parse_speed_knots)(data)?;
+    let (data, wind_gust) = map(take_bits(7u8), parse_speed_knots)(data)?;
+    let (data, wind_direction) = map(take_bits(9u16), parse_direction)(data)?;
+    let (data, wind_gust_direction) = map(take_bits(9u16), parse_direction)(data)?;
+    let (data, description) = parse_description(data)?;
+    let (data, forecast_wind_speed) = map(take_bits(7u8), parse_speed_knots)(data)?;
+    let (data, forecast_wind_gust) = map(take_bits(7u8), parse_speed_knots)(data)?;
+    let (data, forecast_wind_direction) = map(take_bits(9u16), parse_direction)(data)?;
+    let (data, forecast_day) = parse_day(data)?;
+    let (data, forecast_hour) = map(parse_hour, parse_hour_24)(data)?;
+    let (data, forecast_minute) = parse_minsec(data)?;
+    let (data, forecast_duration) = map(take_bits(8u8), parse_duration)(data)?;
+    let (data, _spare) = take_bits::<_, u8, _, _>(3u8)(data)?;
+    Ok((
+        data,
+        SensorData::Wind(WindReport {
+            wind_speed,
+            wind_gust,
+            wind_direction,
+            wind_gust_direction,
+            description,
+            forecast_wind_speed,
+            forecast_wind_gust,
+            forecast_wind_direction,
+            forecast_day,
+            forecast_hour,
+            forecast_minute,
+            forecast_duration,
+        }),
+    ))
+}
+
+fn parse_water_level_report(data: (&[u8], usize)) -> IResult<(&[u8], usize), SensorData> {
+    let (data, is_depth) = map(take_bits(1u8), u8_to_bool)(data)?;
+    let (data, water_level) = map(|data| signed_i32(data, 16), parse_water_level)(data)?;
+    let (data, trend) = map(take_bits(2u8), Tendency::parse)(data)?;
+    let (data, vertical_datum) = take_bits(5u8)(data)?;
+    let (data, description) = parse_description(data)?;
+    let (data, forecast_is_depth) = map(take_bits(1u8), u8_to_bool)(data)?;
+    let (data, forecast_water_level) = map(|data| signed_i32(data, 16), parse_water_level)(data)?;
+    let (data, forecast_day) = parse_day(data)?;
+    let (data, forecast_hour) = map(parse_hour, parse_hour_24)(data)?;
+    let (data, forecast_minute) = parse_minsec(data)?;
+    let (data, forecast_duration) = map(take_bits(8u8), parse_duration)(data)?;
+    let (data, _spare) = take_bits::<_, u32, _, _>(17u8)(data)?;
+    Ok((
+        data,
+        SensorData::WaterLevel(WaterLevelReport {
+            is_depth,
+            water_level,
+            trend,
+            vertical_datum,
+            description,
+            forecast_is_depth,
+            forecast_water_level,
+            forecast_day,
+            forecast_hour,
+            forecast_minute,
+            forecast_duration,
+        }),
+    ))
+}
+
+fn parse_current_flow_2d(data: (&[u8], usize)) -> IResult<(&[u8], usize), SensorData> {
+    let (data, current_1) = parse_current(data, 251, true)?;
+    let (data, current_2) = parse_current(data, 251, true)?;
+    let (data, current_3) = parse_current(data, 251, true)?;
+    let (data, description) = parse_description(data)?;
+    let (data, _spare) = take_bits::<_, u16, _, _>(16u8)(data)?;
+    Ok((
+        data,
+        SensorData::CurrentFlow2d {
+            currents: [current_1, current_2, current_3],
+            description,
+        },
+    ))
+}
+
+fn parse_sea_state(data: (&[u8], usize)) -> IResult<(&[u8], usize), SensorData> {
+    let (data, swell) = parse_waves(data, 251)?;
+    let (data, sea_state) = map(take_bits(4u8), |state| up_to(state, 12))(data)?;
+    let (data, swell_description) = parse_description(data)?;
+    let (data, water_temperature) =
+        map(|data| signed_i32(data, 10), |temp| signed_tenths(temp, 501))(data)?;
+    let (data, water_temperature_depth) =
+        map(take_bits(7u16), |depth| tenths(depth, 126, 0))(data)?;
+    let (data, water_temperature_description) = parse_description(data)?;
+    let (data, waves) = parse_waves(data, 251)?;
+    let (data, waves_description) = parse_description(data)?;
+    let (data, salinity) = map(take_bits(9u16), |salinity| tenths(salinity, 500, 0))(data)?;
+    Ok((
+        data,
+        SensorData::SeaState(SeaStateReport {
+            swell,
+            sea_state,
+            swell_description,
+            water_temperature,
+            water_temperature_depth,
+            water_temperature_description,
+            waves,
+            waves_description,
+            salinity,
+        }),
+    ))
+}
+
+fn parse_sensor_report(data: (&[u8], usize)) -> IResult<(&[u8], usize), SensorReport> {
+    let (data, report_type) = take_bits(4u8)(data)?;
+    let (data, day) = parse_day(data)?;
+    let (data, hour) = map(parse_hour, parse_hour_24)(data)?;
+    let (data, minute) = parse_minsec(data)?;
+    let (data, site_id) = take_bits(7u8)(data)?;
+    let (data, sensor_data) = match report_type {
+        0 => parse_site_location(data)?,
+        1 => parse_station_id(data)?,
+        2 => parse_wind(data)?,
+        3 => parse_water_level_report(data)?,
+        4 => parse_current_flow_2d(data)?,
+        7 => parse_sea_state(data)?,
+        _ => map(take_bits(85u8), |raw| SensorData::Raw {
+            report_type,
+            data: raw,
+        })(data)?,
+    };
+    Ok((
+        data,
+        SensorReport {
+            day,
+            hour,
+            minute,
+            site_id,
+            data: sensor_data,
+        },
+    ))
+}
+
+fn parse_message(data: &[u8]) -> IResult<&[u8], SensorReportList> {
+    bits(move |mut data| -> IResult<_, _> {
+        let mut reports = SensorReportList::default();
+        while remaining_bits(data) >= SENSOR_REPORT_BITS && reports.len() < MAX_SENSOR_REPORTS {
+            let (remaining, report) = parse_sensor_report(data)?;
+            push_unwrap(&mut reports, report);
+            data = remaining;
+        }
+        Ok((data, reports))
+    })(data)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unreadable_literal)]
+    use super::*;
+    use crate::messages::binary::BinaryPayload;
+    use crate::messages::binary_broadcast_message::BinaryBroadcastMessage;
+    use crate::messages::bit_writer::BitWriter;
+    use crate::messages::AisMessageType;
+    use crate::test_helpers::*;
+
+    fn report_header(message: &mut BitWriter, report_type: u32) {
+        message
+            .u32(report_type, 4)
+            .u32(12, 5)
+            .u32(18, 5)
+            .u32(45, 6)
+            .u32(3, 7);
+    }
+
+    #[test]
+    fn test_sensor_reports() {
+        let mut message = BitWriter::new();
+        message
+            .u32(8, 6)
+            .u32(0, 2)
+            .u32(3669987, 30)
+            .u32(0, 2)
+            .u32(1, 10)
+            .u32(26, 6);
+        report_header(&mut message, 0);
+        message
+            .i32(-4_416_000, 25)
+            .i32(2_625_000, 24)
+            .u32(3, 3)
+            .u32(125, 12)
+            .u32(1, 4)
+            .u32(2, 3)
+            .u32(0, 14);
+        report_header(&mut message, 2);
+        message
+            .u32(15, 7)
+            .u32(22, 7)
+            .u32(270, 9)
+            .u32(280, 9)
+            .u32(1, 3)
+            .u32(127, 7)
+            .u32(127, 7)
+            .u32(360, 9)
+            .u32(0, 5)
+            .u32(24, 5)
+            .u32(60, 6)
+            .u32(255, 8)
+            .u32(0, 3);
+        // Weather reports aren't decoded
+        report_header(&mut message, 9);
+        message.u32(0x1234, 32).u32(0, 32).u32(1, 21);
+        assert_eq!(message.bit_len(), 56 + 3 * SENSOR_REPORT_BITS);
+        let report = BinaryBroadcastMessage::parse(message.as_bytes()).unwrap();
+        let reports = match report.decode_payload() {
+            BinaryPayload::SensorReports(reports) => reports,
+            other => panic!("Expected sensor reports, got {:?}", other),
+        };
+        assert_eq!(reports.len(), 3);
+        assert_eq!(reports[0].day, Some(12));
+        assert_eq!(reports[0].hour, Some(18));
+        assert_eq!(reports[0].minute, Some(45));
+        assert_eq!(reports[0].site_id, 3);
+        let site = match &reports[0].data {
+            SensorData::SiteLocation(site) => *site,
+            other => panic!("Expected site location, got {:?}", other),
+        };
+        f32_equal_naive(site.longitude.unwrap(), -73.6);
+        f32_equal_naive(site.latitude.unwrap(), 43.75);
+        assert_eq!(site.precision, 3);
+        f32_equal_naive(site.altitude.unwrap(), 12.5);
+        assert_eq!(site.owner, 1);
+        assert_eq!(site.data_timeout, Some(60));
+        let wind = match &reports[1].data {
+            SensorData::Wind(wind) => *wind,
+            other => panic!("Expected wind report, got {:?}", other),
+        };
+        assert_eq!(wind.wind_speed, Some(15));
+        assert_eq!(wind.wind_gust, Some(22));
+        assert_eq!(wind.wind_direction, Some(270));
+        assert_eq!(wind.wind_gust_direction, Some(280));
+        assert_eq!(wind.description, Some(SensorDataDescription::RawRealTime));
+        assert_eq!(wind.forecast_wind_speed, None);
+        assert_eq!(wind.forecast_wind_direction, None);
+        assert_eq!(wind.forecast_day, None);
+        assert_eq!(wind.forecast_hour, None);
+        assert_eq!(wind.forecast_minute, None);
+        assert_eq!(wind.forecast_duration, None);
+        assert_eq!(
+            reports[2].data,
+            SensorData::Raw {
+                report_type: 9,
+                data: (0x1234 << 53) | 1,
+            }
+        );
+    }
+
+    #[test]
+    fn test_water_level_and_current() {
+        let mut message = BitWriter::new();
+        report_header(&mut message, 3);
+        message
+            .bool(false)
+            .i32(-18, 16)
+            .u32(2, 2)
+            .u32(1, 5)
+            .u32(2, 3)
+            .bool(false)
+            .i32(-32768, 16)
+            .u32(0, 5)
+            .u32(24, 5)
+            .u32(60, 6)
+            .u32(255, 8)
+            .u32(0, 17);
+        report_header(&mut message, 4);
+        message
+            .u32(12, 8)
+            .u32(90, 9)
+            .u32(2, 5)
+            .u32(255, 8)
+            .u32(360, 9)
+            .u32(31, 5)
+            .u32(255, 8)
+            .u32(360, 9)
+            .u32(31, 5)
+            .u32(7, 3)
+            .u32(0, 16);
+        let reports = parse_sensor_reports(message.as_bytes()).unwrap();
+        assert_eq!(reports.len(), 2);
+        let level = match &reports[0].data {
+            SensorData::WaterLevel(level) => *level,
+            other => panic!("Expected water level, got {:?}", other),
+        };
+        assert!(!level.is_depth);
+        f32_equal_naive(level.water_level.unwrap(), -0.18);
+        assert_eq!(level.trend, Some(Tendency::Increasing));
+        assert_eq!(level.vertical_datum, 1);
+        assert_eq!(
+            level.description,
+            Some(SensorDataDescription::RealTimeWithQualityControl)
+        );
+        assert_eq!(level.forecast_water_level, None);
+        match &reports[1].data {
+            SensorData::CurrentFlow2d {
+                currents,
+                description,
+            } => {
+                f32_equal_naive(currents[0].speed.unwrap(), 1.2);
+                assert_eq!(currents[0].direction, Some(90));
+                assert_eq!(currents[0].depth, Some(2));
+                assert_eq!(currents[1].speed, None);
+                assert_eq!(currents[2].depth, None);
+                assert_eq!(*description, None);
+            }
+            other => panic!("Expected current flow, got {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,203 @@
+//! Inland ship static and voyage related data (European inland AIS, DAC 200, FID 10)
+use super::super::parsers::*;
+use crate::errors::Result;
+use nom::bits::{bits, complete::take as take_bits};
+use nom::combinator::map;
+use nom::IResult;
+
+/// Hazardous cargo, as signalled by blue cones or flags
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum InlandHazard {
+    /// Number of blue cones, from 0 to 3
+    BlueCones(u8),
+    /// B-flag
+    BFlag,
+}
+
+impl InlandHazard {
+    pub fn parse(data: u8) -> Option<Self> {
+        match data {
+            0..=3 => Some(Self::BlueCones(data)),
+            4 => Some(Self::BFlag),
+            _ => None,
+        }
+    }
+}
+
+/// Static and voyage data of an inland vessel, broadcast (type 8) alongside the
+/// usual type 5 message to add the details used on European inland waterways
+#[derive(Debug, PartialEq)]
+pub struct InlandStaticAndVoyageData {
+    /// European Number of Identification (ENI)
+    pub vessel_id: AsciiString,
+    /// Length of the ship or convoy in metres, sent in decimetres
+    pub length: Option<f32>,
+    /// Beam in metres, sent in decimetres
+    pub beam: Option<f32>,
+    /// Ship or combination type, as an ERI code
+    pub ship_type: Option<u16>,
+    pub hazard: Option<InlandHazard>,
+    /// Draught in metres, sent in centimetres
+    pub draught: Option<f32>,
+    pub loaded: Option<bool>,
+    /// Set if the speed is from a high quality source, rather than GNSS
+    pub high_speed_quality: bool,
+    pub high_course_quality: bool,
+    pub high_heading_quality: bool,
+}
+
+impl InlandStaticAndVoyageData {
+    pub fn parse(data: &[u8]) -> Result<Self> {
+        let (_, report) = parse_message(data)?;
+        Ok(report)
+    }
+}
+
+/// Values in `divisor`ths of a metre, with 0 meaning not available
+fn parse_metres(data: u16, divisor: f32) -> Option<f32> {
+    match data {
+        0 => None,
+        _ => Some(data as f32 / divisor),
+    }
+}
+
+fn parse_ship_type(data: u16) -> Option<u16> {
+    match data {
+        0 => None,
+        _ => Some(data),
+    }
+}
+
+fn parse_loaded(data: u8) -> Option<bool> {
+    match data {
+        1 => Some(true),
+        2 => Some(false),
+        _ => None,
+    }
+}
+
+fn parse_message(data: &[u8]) -> IResult<&[u8], InlandStaticAndVoyageData> {
+    bits(move |data| -> IResult<_, _> {
+        let (data, vessel_id) = parse_6bit_ascii(data, 48)?;
+        let (data, length) = map(take_bits(13u16), |length| parse_metres(length, 10.0))(data)?;
+        let (data, beam) = map(take_bits(10u16), |beam| parse_metres(beam, 10.0))(data)?;
+        let (data, ship_type) = map(take_bits(14u16), parse_ship_type)(data)?;
+        let (data, hazard) = map(take_bits(3u8), InlandHazard::parse)(data)?;
+        let (data, draught) = map(take_bits(11u16), |draught| parse_metres(draught, 100.0))(data)?;
+        let (data, loaded) = map(take_bits(2u8), parse_loaded)(data)?;
+        let (data, high_speed_quality) = map(take_bits(1u8), u8_to_bool)(data)?;
+        let (data, high_course_quality) = map(take_bits(1u8), u8_to_bool)(data)?;
+        let (data, high_heading_quality) = map(take_bits(1u8), u8_to_bool)(data)?;
+        let (data, _spare) = take_bits::<_, u8, _, _>(8u8)(data)?;
+        Ok((
+            data,
+            InlandStaticAndVoyageData {
+                vessel_id,
+                length,
+                beam,
+                ship_type,
+                hazard,
+                draught,
+                loaded,
+                high_speed_quality,
+                high_course_quality,
+                high_heading_quality,
+            },
+        ))
+    })(data)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unreadable_literal)]
+    use super::*;
+    use crate::messages::binary::BinaryPayload;
+    use crate::messages::binary_broadcast_message::BinaryBroadcastMessage;
+    use crate::messages::bit_writer::BitWriter;
+    use crate::messages::AisMessageType;
+    use crate::test_helpers::*;
+
+    #[test]
+    fn test_inland_static() {
+        let mut message = BitWriter::new();
+        message
+            .u32(8, 6)
+            .u32(0, 2)
+            .u32(211512340, 30)
+            .u32(0, 2)
+            .u32(200, 10)
+            .u32(10, 6);
+        message
+            .string6("04801234", 48)
+            .u32(1105, 13)
+            .u32(114, 10)
+            .u32(8021, 14)
+            .u32(1, 3)
+            .u32(275, 11)
+            .u32(1, 2)
+            .bool(true)
+            .bool(false)
+            .bool(true)
+            .u32(0, 8);
+        assert_eq!(message.bit_len(), 168);
+        let report = BinaryBroadcastMessage::parse(message.as_bytes()).unwrap();
+        let inland = match report.decode_payload() {
+            BinaryPayload::InlandStaticAndVoyageData(inland) => inland,
+            other => panic!("Expected inland static data, got {:?}", other),
+        };
+        assert_eq!(inland.vessel_id, "04801234");
+        f32_equal_naive(inland.length.unwrap(), 110.5);
+        f32_equal_naive(inland.beam.unwrap(), 11.4);
+        assert_eq!(inland.ship_type, Some(8021));
+        assert_eq!(inland.hazard, Some(InlandHazard::BlueCones(1)));
+        f32_equal_naive(inland.draught.unwrap(), 2.75);
+        assert_eq!(inland.loaded, Some(true));
+        assert!(inland.high_speed_quality);
+        assert!(!inland.high_course_quality);
+        assert!(inland.high_heading_quality);
+    }
+
+    #[test]
+    fn test_inland_static_sample() {
+        // !AIVDM,1,1,,A,85Mv070j2d>=<e<<=PQhhg`59P00,0*26
+        let bitstream = crate::messages::unarmor(b"85Mv070j2d>=<e<<=PQhhg`59P00", 0).unwrap();
+        let report = BinaryBroadcastMessage::parse(bitstream.as_ref()).unwrap();
+        assert_eq!(report.mmsi, 366968860);
+        let inland = match report.decode_payload() {
+            BinaryPayload::InlandStaticAndVoyageData(inland) => inland,
+            other => panic!("Expected inland static data, got {:?}", other),
+        };
+        assert_eq!(inland.vessel_id, "08424006");
+        // Length and beam are sent in decimetres, and draught in centimetres
+        f32_equal_naive(inland.length.unwrap(), 27.0);
+        f32_equal_naive(inland.beam.unwrap(), 9.7);
+        f32_equal_naive(inland.draught.unwrap(), 3.04);
+        assert_eq!(inland.ship_type, Some(8000));
+        assert_eq!(inland.hazard, None);
+        assert_eq!(inland.loaded, None);
+        assert!(!inland.high_speed_quality);
+    }
+
+    #[test]
+    fn test_inland_static_not_available() {
+        let mut message = BitWriter::new();
+        message
+            .string6("", 48)
+            .u32(0, 13)
+            .u32(0, 10)
+            .u32(0, 14)
+            .u32(5, 3)
+            .u32(0, 11)
+            .u32(0, 2)
+            .u32(0, 3)
+            .u32(0, 8);
+        let inland = InlandStaticAndVoyageData::parse(message.as_bytes()).unwrap();
+        assert_eq!(inland.vessel_id, "");
+        assert_eq!(inland.length, None);
+        assert_eq!(inland.beam, None);
+        assert_eq!(inland.ship_type, None);
+        assert_eq!(inland.hazard, None);
+        assert_eq!(inland.draught, None);
+        assert_eq!(inland.loaded, None);
+    }
+}
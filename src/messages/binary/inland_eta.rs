@@ -0,0 +1,167 @@
+//! ETA at lock/bridge/terminal (European inland AIS, DAC 200, FID 21)
+use super::super::parsers::*;
+use super::parse_hour_24;
+use crate::errors::Result;
+use nom::bits::{bits, complete::take as take_bits};
+use nom::combinator::map;
+use nom::IResult;
+
+/// Estimated time of arrival at a lock, bridge, or terminal, sent addressed (type 6)
+/// by inland vessels to the authority operating it. The location is identified by
+/// its ISRS code, split into the fields below.
+#[derive(Debug, PartialEq)]
+pub struct EtaAtLockBridgeTerminal {
+    /// UN country code
+    pub country: AsciiString,
+    /// UN/LOCODE of the location, without the country
+    pub locode: AsciiString,
+    pub fairway_section: AsciiString,
+    /// Code of the lock, bridge, or terminal
+    pub terminal: AsciiString,
+    /// Position along the fairway, in hectometres
+    pub fairway_hectometre: AsciiString,
+    pub eta_month: Option<u8>,
+    pub eta_day: Option<u8>,
+    pub eta_hour: Option<u8>,
+    pub eta_minute: Option<u8>,
+    /// Number of assisting tugboats
+    pub tugboats: Option<u8>,
+    /// Air draught in metres, from 0.01 to 40.00
+    pub air_draught: Option<f32>,
+}
+
+impl EtaAtLockBridgeTerminal {
+    pub fn parse(data: &[u8]) -> Result<Self> {
+        let (_, report) = parse_message(data)?;
+        Ok(report)
+    }
+}
+
+/// Values of 7 mean the number is unknown
+fn parse_tugboats(data: u8) -> Option<u8> {
+    match data {
+        7 => None,
+        _ => Some(data),
+    }
+}
+
+/// Air draught in centimetres, with 0 meaning not available
+fn parse_air_draught(data: u16) -> Option<f32> {
+    match data {
+        0 => None,
+        _ => Some(data as f32 / 100.0),
+    }
+}
+
+fn parse_message(data: &[u8]) -> IResult<&[u8], EtaAtLockBridgeTerminal> {
+    bits(move |data| -> IResult<_, _> {
+        let (data, country) = parse_6bit_ascii(data, 12)?;
+        let (data, locode) = parse_6bit_ascii(data, 18)?;
+        let (data, fairway_section) = parse_6bit_ascii(data, 30)?;
+        let (data, terminal) = parse_6bit_ascii(data, 30)?;
+        let (data, fairway_hectometre) = parse_6bit_ascii(data, 30)?;
+        let (data, eta_month) = parse_month(data)?;
+        let (data, eta_day) = parse_day(data)?;
+        let (data, eta_hour) = map(parse_hour, parse_hour_24)(data)?;
+        let (data, eta_minute) = parse_minsec(data)?;
+        let (data, tugboats) = map(take_bits(3u8), parse_tugboats)(data)?;
+        let (data, air_draught) = map(take_bits(12u16), parse_air_draught)(data)?;
+        let (data, _spare) = take_bits::<_, u8, _, _>(5u8)(data)?;
+        Ok((
+            data,
+            EtaAtLockBridgeTerminal {
+                country,
+                locode,
+                fairway_section,
+                terminal,
+                fairway_hectometre,
+                eta_month,
+                eta_day,
+                eta_hour,
+                eta_minute,
+                tugboats,
+                air_draught,
+            },
+        ))
+    })(data)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unreadable_literal)]
+    use super::*;
+    use crate::messages::binary::BinaryPayload;
+    use crate::messages::binary_addressed::BinaryAddressedMessage;
+    use crate::messages::bit_writer::BitWriter;
+    use crate::messages::AisMessageType;
+    use crate::test_helpers::*;
+
+    #[test]
+    fn test_inland_eta() {
+        let mut message = BitWriter::new();
+        message
+            .u32(6, 6)
+            .u32(0, 2)
+            .u32(244670316, 30)
+            .u32(0, 2)
+            .u32(2442012, 30)
+            .bool(false)
+            .bool(false)
+            .u32(200, 10)
+            .u32(21, 6);
+        message
+            .string6("NL", 12)
+            .string6("AMS", 18)
+            .string6("00123", 30)
+            .string6("L0042", 30)
+            .string6("00157", 30)
+            .u32(6, 4)
+            .u32(21, 5)
+            .u32(14, 5)
+            .u32(45, 6)
+            .u32(1, 3)
+            .u32(1250, 12)
+            .u32(0, 5);
+        let report = BinaryAddressedMessage::parse(message.as_bytes()).unwrap();
+        let eta = match report.decode_payload() {
+            BinaryPayload::EtaAtLockBridgeTerminal(eta) => eta,
+            other => panic!("Expected inland ETA, got {:?}", other),
+        };
+        assert_eq!(eta.country, "NL");
+        assert_eq!(eta.locode, "AMS");
+        assert_eq!(eta.fairway_section, "00123");
+        assert_eq!(eta.terminal, "L0042");
+        assert_eq!(eta.fairway_hectometre, "00157");
+        assert_eq!(eta.eta_month, Some(6));
+        assert_eq!(eta.eta_day, Some(21));
+        assert_eq!(eta.eta_hour, Some(14));
+        assert_eq!(eta.eta_minute, Some(45));
+        assert_eq!(eta.tugboats, Some(1));
+        f32_equal_naive(eta.air_draught.unwrap(), 12.5);
+    }
+
+    #[test]
+    fn test_inland_eta_not_available() {
+        let mut message = BitWriter::new();
+        message
+            .string6("", 12)
+            .string6("", 18)
+            .string6("", 30)
+            .string6("", 30)
+            .string6("", 30)
+            .u32(0, 4)
+            .u32(0, 5)
+            .u32(24, 5)
+            .u32(60, 6)
+            .u32(7, 3)
+            .u32(0, 12)
+            .u32(0, 5);
+        let eta = EtaAtLockBridgeTerminal::parse(message.as_bytes()).unwrap();
+        assert_eq!(eta.eta_month, None);
+        assert_eq!(eta.eta_day, None);
+        assert_eq!(eta.eta_hour, None);
+        assert_eq!(eta.eta_minute, None);
+        assert_eq!(eta.tugboats, None);
+        assert_eq!(eta.air_draught, None);
+    }
+}
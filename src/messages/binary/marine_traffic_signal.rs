@@ -0,0 +1,144 @@
+//! Marine traffic signal (IMO SN.1/Circ.289, DAC 1, FID 19)
+use super::super::parsers::*;
+use super::{parse_hour_24, parse_latitude_min_1000, parse_longitude_min_1000};
+use crate::errors::Result;
+use nom::bits::{bits, complete::take as take_bits};
+use nom::combinator::map;
+use nom::IResult;
+
+/// Status of a traffic signal
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum SignalStatus {
+    InRegularService,
+    InIrregularService,
+    Reserved,
+}
+
+impl SignalStatus {
+    pub fn parse(data: u8) -> Option<Self> {
+        match data {
+            0 => None,
+            1 => Some(Self::InRegularService),
+            2 => Some(Self::InIrregularService),
+            3 => Some(Self::Reserved),
+            _ => unreachable!(),
+        }
+    }
+}
+
+/// Current and upcoming signal shown at a traffic signal station, such as at a
+/// port entrance or lock
+#[derive(Debug, PartialEq)]
+pub struct MarineTrafficSignal {
+    /// Links this message to related messages
+    pub linkage_id: u16,
+    /// Name of the signal station
+    pub station_name: AsciiString,
+    pub longitude: Option<f32>,
+    pub latitude: Option<f32>,
+    pub status: Option<SignalStatus>,
+    /// Signal currently shown. Values are defined in table 4.2 of IMO SN.1/Circ.289;
+    /// for example, 2 means vessels shall not proceed, and 4 means vessels may
+    /// proceed with two-way traffic.
+    pub signal: Option<u8>,
+    /// UTC hour of the next signal shift
+    pub next_shift_hour: Option<u8>,
+    /// UTC minute of the next signal shift
+    pub next_shift_minute: Option<u8>,
+    /// Expected next signal, using the same values as `signal`
+    pub next_signal: Option<u8>,
+}
+
+impl MarineTrafficSignal {
+    pub fn parse(data: &[u8]) -> Result<Self> {
+        let (_, report) = parse_message(data)?;
+        Ok(report)
+    }
+}
+
+/// Signal codes, with 0 meaning not available
+fn parse_signal(data: u8) -> Option<u8> {
+    match data {
+        0 => None,
+        _ => Some(data),
+    }
+}
+
+fn parse_message(data: &[u8]) -> IResult<&[u8], MarineTrafficSignal> {
+    bits(move |data| -> IResult<_, _> {
+        let (data, linkage_id) = take_bits(10u16)(data)?;
+        let (data, station_name) = parse_6bit_ascii(data, 120)?;
+        let (data, longitude) = map(|data| signed_i32(data, 25), parse_longitude_min_1000)(data)?;
+        let (data, latitude) = map(|data| signed_i32(data, 24), parse_latitude_min_1000)(data)?;
+        let (data, status) = map(take_bits(2u8), SignalStatus::parse)(data)?;
+        let (data, signal) = map(take_bits(5u8), parse_signal)(data)?;
+        let (data, next_shift_hour) = map(parse_hour, parse_hour_24)(data)?;
+        let (data, next_shift_minute) = parse_minsec(data)?;
+        let (data, next_signal) = map(take_bits(5u8), parse_signal)(data)?;
+        Ok((
+            data,
+            MarineTrafficSignal {
+                linkage_id,
+                station_name,
+                longitude,
+                latitude,
+                status,
+                signal,
+                next_shift_hour,
+                next_shift_minute,
+                next_signal,
+            },
+        ))
+    })(data)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unreadable_literal)]
+    use super::*;
+    use crate::messages::binary::BinaryPayload;
+    use crate::messages::binary_broadcast_message::BinaryBroadcastMessage;
+    use crate::messages::bit_writer::BitWriter;
+    use crate::messages::AisMessageType;
+    use crate::test_helpers::*;
+
+    #[test]
+    fn test_marine_traffic_signal() {
+        let mut message = BitWriter::new();
+        message
+            .u32(8, 6)
+            .u32(0, 2)
+            .u32(2470012, 30)
+            .u32(0, 2)
+            .u32(1, 10)
+            .u32(19, 6);
+        message
+            .u32(102, 10)
+            .string6("KIEL HOLTENAU", 120)
+            .i32(608_100, 25)
+            .i32(3_262_200, 24)
+            .u32(1, 2)
+            .u32(2, 5)
+            .u32(14, 5)
+            .u32(45, 6)
+            .u32(4, 5)
+            .u32(0, 32)
+            .u32(0, 32)
+            .u32(0, 32)
+            .u32(0, 6);
+        let report = BinaryBroadcastMessage::parse(message.as_bytes()).unwrap();
+        let signal = match report.decode_payload() {
+            BinaryPayload::MarineTrafficSignal(signal) => signal,
+            other => panic!("Expected marine traffic signal, got {:?}", other),
+        };
+        assert_eq!(signal.linkage_id, 102);
+        assert_eq!(signal.station_name, "KIEL HOLTENAU");
+        f32_equal_naive(signal.longitude.unwrap(), 10.135);
+        f32_equal_naive(signal.latitude.unwrap(), 54.37);
+        assert_eq!(signal.status, Some(SignalStatus::InRegularService));
+        assert_eq!(signal.signal, Some(2));
+        assert_eq!(signal.next_shift_hour, Some(14));
+        assert_eq!(signal.next_shift_minute, Some(45));
+        assert_eq!(signal.next_signal, Some(4));
+    }
+}
@@ -0,0 +1,469 @@
+//! Meteorological and hydrological data (IMO SN.1/Circ.289, DAC 1, FID 31; formerly
+//! IMO SN/Circ.236, DAC 1, FID 11)
+use super::super::navigation::Accuracy;
+use super::super::parsers::*;
+use super::{parse_hour_24, parse_latitude_min_1000, parse_longitude_min_1000};
+use crate::errors::Result;
+use nom::bits::{bits, complete::take as take_bits};
+use nom::combinator::{cond, map};
+use nom::IResult;
+
+/// Layout a meteorological and hydrological report was decoded from
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum MeteoHydroFormat {
+    /// IMO SN/Circ.236, DAC 1, FID 11
+    Legacy,
+    /// IMO SN.1/Circ.289, DAC 1, FID 31
+    Current,
+}
+
+/// Direction in which a pressure or water level is changing
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum Tendency {
+    Steady,
+    Decreasing,
+    Increasing,
+}
+
+impl Tendency {
+    pub fn parse(data: u8) -> Option<Self> {
+        match data {
+            0 => Some(Self::Steady),
+            1 => Some(Self::Decreasing),
+            2 => Some(Self::Increasing),
+            3 => None,
+            _ => unreachable!(),
+        }
+    }
+}
+
+/// Type of precipitation, per WMO code table 4.201
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum Precipitation {
+    Rain,
+    Thunderstorm,
+    FreezingRain,
+    MixedOrIce,
+    Snow,
+    Reserved(u8),
+}
+
+impl Precipitation {
+    pub fn parse(data: u8) -> Option<Self> {
+        match data {
+            1 => Some(Self::Rain),
+            2 => Some(Self::Thunderstorm),
+            3 => Some(Self::FreezingRain),
+            4 => Some(Self::MixedOrIce),
+            5 => Some(Self::Snow),
+            0 | 6 => Some(Self::Reserved(data)),
+            7 => None,
+            _ => unreachable!(),
+        }
+    }
+}
+
+/// Current measured at one depth
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct Current {
+    /// Speed in knots
+    pub speed: Option<f32>,
+    /// Direction in degrees
+    pub direction: Option<u16>,
+    /// Depth of the measurement in metres; not sent for the surface current
+    pub depth: Option<u8>,
+}
+
+/// Waves or swell
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct Waves {
+    /// Significant height in metres
+    pub height: Option<f32>,
+    /// Period in seconds
+    pub period: Option<u8>,
+    /// Direction in degrees
+    pub direction: Option<u16>,
+}
+
+/// Weather and sea conditions reported by a station, such as a base station or
+/// an aid to navigation fitted with sensors. Both the current format (FID 31) and
+/// the older one it replaces (FID 11) are decoded, and `format` records which
+/// one a report was sent in; fields the older format lacks are `None` or `false`.
+#[derive(Debug, PartialEq)]
+pub struct MeteoHydro {
+    pub format: MeteoHydroFormat,
+    pub longitude: Option<f32>,
+    pub latitude: Option<f32>,
+    pub fix_quality: Option<Accuracy>,
+    /// UTC day of the observation
+    pub day: Option<u8>,
+    pub hour: Option<u8>,
+    pub minute: Option<u8>,
+    /// Average wind speed over the last 10 minutes, in knots. In the current
+    /// format, 126 means 126 knots or more.
+    pub wind_speed: Option<u8>,
+    /// Wind gust speed over the last 10 minutes, in knots
+    pub wind_gust: Option<u8>,
+    /// Wind direction in degrees
+    pub wind_direction: Option<u16>,
+    pub wind_gust_direction: Option<u16>,
+    /// Air temperature in degrees Celsius
+    pub air_temperature: Option<f32>,
+    /// Relative humidity in percent
+    pub relative_humidity: Option<u8>,
+    /// Dew point in degrees Celsius
+    pub dew_point: Option<f32>,
+    /// Air pressure in hPa. In the current format, 799 means 799 hPa or less, and
+    /// 1201 means 1201 hPa or more.
+    pub air_pressure: Option<u16>,
+    pub pressure_tendency: Option<Tendency>,
+    /// Horizontal visibility in nautical miles
+    pub visibility: Option<f32>,
+    /// Set if the visibility is greater than reported, as it is beyond the range
+    /// that can be measured
+    pub visibility_greater: bool,
+    /// Water level, including tide, relative to the local chart datum, in metres
+    pub water_level: Option<f32>,
+    pub water_level_trend: Option<Tendency>,
+    pub surface_current: Current,
+    pub current_2: Current,
+    pub current_3: Current,
+    pub waves: Waves,
+    pub swell: Waves,
+    /// Sea state on the Beaufort scale
+    pub sea_state: Option<u8>,
+    /// Water temperature in degrees Celsius
+    pub water_temperature: Option<f32>,
+    pub precipitation: Option<Precipitation>,
+    /// Salinity in parts per thousand
+    pub salinity: Option<f32>,
+    pub ice: Option<bool>,
+}
+
+impl MeteoHydro {
+    /// Parses the current format (FID 31)
+    pub fn parse(data: &[u8]) -> Result<Self> {
+        let (_, report) = parse_message(data)?;
+        Ok(report)
+    }
+
+    /// Parses the older format (FID 11)
+    pub fn parse_legacy(data: &[u8]) -> Result<Self> {
+        let (_, report) = parse_legacy_message(data)?;
+        Ok(report)
+    }
+}
+
+/// Values from 0 to `max` in tenths, offset by `offset` tenths; higher values mean
+/// not available
+pub(super) fn tenths(data: u16, max: u16, offset: i32) -> Option<f32> {
+    (data <= max).then(|| (data as i32 + offset) as f32 / 10.0)
+}
+
+/// Signed values in tenths, with `not_available` meaning not available
+pub(super) fn signed_tenths(data: i32, not_available: i32) -> Option<f32> {
+    (data != not_available).then(|| data as f32 / 10.0)
+}
+
+/// Values from 0 to `max`; higher values mean not available
+pub(super) fn up_to<T: PartialOrd>(data: T, max: T) -> Option<T> {
+    (data <= max).then_some(data)
+}
+
+pub(super) fn parse_direction(data: u16) -> Option<u16> {
+    up_to(data, 359)
+}
+
+fn parse_ice(data: u8) -> Option<bool> {
+    match data {
+        0 => Some(false),
+        1 => Some(true),
+        _ => None,
+    }
+}
+
+/// Current speed in tenths of a knot, and direction, with `max_speed` the highest
+/// valid speed. Only currents below the surface have a depth.
+pub(super) fn parse_current(
+    data: (&[u8], usize),
+    max_speed: u16,
+    has_depth: bool,
+) -> IResult<(&[u8], usize), Current> {
+    let (data, speed) = map(take_bits(8u16), |speed| tenths(speed, max_speed, 0))(data)?;
+    let (data, direction) = map(take_bits(9u16), parse_direction)(data)?;
+    let (data, depth) = cond(has_depth, map(take_bits(5u8), |depth| up_to(depth, 30)))(data)?;
+    Ok((
+        data,
+        Current {
+            speed,
+            direction,
+            depth: depth.flatten(),
+        },
+    ))
+}
+
+/// Wave or swell height in tenths of a metre, with `max_height` the highest valid
+/// height, followed by the period and direction
+pub(super) fn parse_waves(data: (&[u8], usize), max_height: u16) -> IResult<(&[u8], usize), Waves> {
+    let (data, height) = map(take_bits(8u16), |height| tenths(height, max_height, 0))(data)?;
+    let (data, period) = map(take_bits(6u8), |period| up_to(period, 60))(data)?;
+    let (data, direction) = map(take_bits(9u16), parse_direction)(data)?;
+    Ok((
+        data,
+        Waves {
+            height,
+            period,
+            direction,
+        },
+    ))
+}
+
+fn parse_message(data: &[u8]) -> IResult<&[u8], MeteoHydro> {
+    bits(move |data| -> IResult<_, _> {
+        let (data, longitude) = map(|data| signed_i32(data, 25), parse_longitude_min_1000)(data)?;
+        let (data, latitude) = map(|data| signed_i32(data, 24), parse_latitude_min_1000)(data)?;
+        let (data, fix_quality) = map(take_bits(1u8), Accuracy::parse)(data)?;
+        let (data, day) = parse_day(data)?;
+        let (data, hour) = map(parse_hour, parse_hour_24)(data)?;
+        let (data, minute) = parse_minsec(data)?;
+        let (data, wind_speed) = map(take_bits(7u8), |speed| up_to(speed, 126))(data)?;
+        let (data, wind_gust) = map(take_bits(7u8), |speed| up_to(speed, 126))(data)?;
+        let (data, wind_direction) = map(take_bits(9u16), parse_direction)(data)?;
+        let (data, wind_gust_direction) = map(take_bits(9u16), parse_direction)(data)?;
+        let (data, air_temperature) = map(
+            |data| signed_i32(data, 11),
+            |temp| signed_tenths(temp, -1024),
+        )(data)?;
+        let (data, relative_humidity) = map(take_bits(7u8), |humidity| up_to(humidity, 100))(data)?;
+        let (data, dew_point) =
+            map(|data| signed_i32(data, 10), |temp| signed_tenths(temp, 501))(data)?;
+        let (data, air_pressure) = map(take_bits(9u16), |pressure| {
+            up_to(pressure, 402).map(|pressure| pressure + 799)
+        })(data)?;
+        let (data, pressure_tendency) = map(take_bits(2u8), Tendency::parse)(data)?;
+        let (data, visibility_greater) = map(take_bits(1u8), u8_to_bool)(data)?;
+        let (data, visibility) =
+            map(take_bits(7u16), |visibility| tenths(visibility, 126, 0))(data)?;
+        let (data, water_level) = map(take_bits(12u16), |level: u16| {
+            up_to(level, 4000).map(|level| (level as i32 - 1000) as f32 / 100.0)
+        })(data)?;
+        let (data, water_level_trend) = map(take_bits(2u8), Tendency::parse)(data)?;
+        let (data, surface_current) = parse_current(data, 251, false)?;
+        let (data, current_2) = parse_current(data, 251, true)?;
+        let (data, current_3) = parse_current(data, 251, true)?;
+        let (data, waves) = parse_waves(data, 251)?;
+        let (data, swell) = parse_waves(data, 251)?;
+        let (data, sea_state) = map(take_bits(4u8), |state| up_to(state, 12))(data)?;
+        let (data, water_temperature) =
+            map(|data| signed_i32(data, 10), |temp| signed_tenths(temp, 501))(data)?;
+        let (data, precipitation) = map(take_bits(3u8), Precipitation::parse)(data)?;
+        let (data, salinity) = map(take_bits(9u16), |salinity| tenths(salinity, 500, 0))(data)?;
+        let (data, ice) = map(take_bits(2u8), parse_ice)(data)?;
+        let (data, _spare) = take_bits::<_, u16, _, _>(10u8)(data)?;
+        Ok((
+            data,
+            MeteoHydro {
+                format: MeteoHydroFormat::Current,
+                longitude,
+                latitude,
+                fix_quality: Some(fix_quality),
+                day,
+                hour,
+                minute,
+                wind_speed,
+                wind_gust,
+                wind_direction,
+                wind_gust_direction,
+                air_temperature,
+                relative_humidity,
+                dew_point,
+                air_pressure,
+                pressure_tendency,
+                visibility,
+                visibility_greater,
+                water_level,
+                water_level_trend,
+                surface_current,
+                current_2,
+                current_3,
+                waves,
+                swell,
+                sea_state,
+                water_temperature,
+                precipitation,
+                salinity,
+                ice,
+            },
+        ))
+    })(data)
+}
+
+/// The older format puts the latitude first, and offsets temperatures and levels
+/// rather than using signed values
+fn parse_legacy_message(data: &[u8]) -> IResult<&[u8], MeteoHydro> {
+    bits(move |data| -> IResult<_, _> {
+        let (data, latitude) = map(|data| signed_i32(data, 24), parse_latitude_min_1000)(data)?;
+        let (data, longitude) = map(|data| signed_i32(data, 25), parse_longitude_min_1000)(data)?;
+        let (data, day) = parse_day(data)?;
+        let (data, hour) = map(parse_hour, parse_hour_24)(data)?;
+        let (data, minute) = parse_minsec(data)?;
+        let (data, wind_speed) = map(take_bits(7u8), |speed| up_to(speed, 126))(data)?;
+        let (data, wind_gust) = map(take_bits(7u8), |speed| up_to(speed, 126))(data)?;
+        let (data, wind_direction) = map(take_bits(9u16), parse_direction)(data)?;
+        let (data, wind_gust_direction) = map(take_bits(9u16), parse_direction)(data)?;
+        let (data, air_temperature) = map(take_bits(11u16), |temp| tenths(temp, 1200, -600))(data)?;
+        let (data, relative_humidity) = map(take_bits(7u8), |humidity| up_to(humidity, 100))(data)?;
+        let (data, dew_point) = map(take_bits(10u16), |temp| tenths(temp, 700, -200))(data)?;
+        let (data, air_pressure) = map(take_bits(9u16), |pressure| {
+            up_to(pressure, 400).map(|pressure| pressure + 800)
+        })(data)?;
+        let (data, pressure_tendency) = map(take_bits(2u8), Tendency::parse)(data)?;
+        let (data, visibility) =
+            map(take_bits(8u16), |visibility| tenths(visibility, 250, 0))(data)?;
+        let (data, water_level) = map(take_bits(9u16), |level| tenths(level, 400, -100))(data)?;
+        let (data, water_level_trend) = map(take_bits(2u8), Tendency::parse)(data)?;
+        let (data, surface_current) = parse_current(data, 250, false)?;
+        let (data, current_2) = parse_current(data, 250, true)?;
+        let (data, current_3) = parse_current(data, 250, true)?;
+        let (data, waves) = parse_waves(data, 250)?;
+        let (data, swell) = parse_waves(data, 250)?;
+        let (data, sea_state) = map(take_bits(4u8), |state| up_to(state, 12))(data)?;
+        let (data, water_temperature) =
+            map(take_bits(10u16), |temp| tenths(temp, 600, -100))(data)?;
+        let (data, precipitation) = map(take_bits(3u8), Precipitation::parse)(data)?;
+        let (data, salinity) = map(take_bits(9u16), |salinity| tenths(salinity, 500, 0))(data)?;
+        let (data, ice) = map(take_bits(2u8), parse_ice)(data)?;
+        let (data, _spare) = take_bits::<_, u8, _, _>(6u8)(data)?;
+        Ok((
+            data,
+            MeteoHydro {
+                format: MeteoHydroFormat::Legacy,
+                longitude,
+                latitude,
+                fix_quality: None,
+                day,
+                hour,
+                minute,
+                wind_speed,
+                wind_gust,
+                wind_direction,
+                wind_gust_direction,
+                air_temperature,
+                relative_humidity,
+                dew_point,
+                air_pressure,
+                pressure_tendency,
+                visibility,
+                visibility_greater: false,
+                water_level,
+                water_level_trend,
+                surface_current,
+                current_2,
+                current_3,
+                waves,
+                swell,
+                sea_state,
+                water_temperature,
+                precipitation,
+                salinity,
+                ice,
+            },
+        ))
+    })(data)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unreadable_literal)]
+    use super::*;
+    use crate::messages::binary::BinaryPayload;
+    use crate::messages::binary_broadcast_message::BinaryBroadcastMessage;
+    use crate::messages::AisMessageType;
+    use crate::test_helpers::*;
+
+    fn decode(bytestream: &[u8], fill_bits: usize) -> MeteoHydro {
+        let bitstream = crate::messages::unarmor(bytestream, fill_bits).unwrap();
+        let report = BinaryBroadcastMessage::parse(bitstream.as_ref()).unwrap();
+        match report.decode_payload() {
+            BinaryPayload::MeteoHydro(meteo) => meteo,
+            other => panic!("Expected meteorological data, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_legacy() {
+        let meteo = decode(
+            b"8@2<HW@0BkdhF0dcH5R`Q@kDJjD;WwfRwwwwwwwwwwwwwwwwwwwwwwwwwt0",
+            2,
+        );
+        assert_eq!(meteo.format, MeteoHydroFormat::Legacy);
+        f32_equal_naive(meteo.latitude.unwrap(), 64.65);
+        f32_equal_naive(meteo.longitude.unwrap(), 24.4);
+        assert_eq!(meteo.fix_quality, None);
+        assert_eq!(meteo.day, Some(22));
+        assert_eq!(meteo.hour, Some(5));
+        assert_eq!(meteo.minute, Some(17));
+        assert_eq!(meteo.wind_speed, Some(5));
+        assert_eq!(meteo.wind_gust, Some(6));
+        assert_eq!(meteo.wind_direction, Some(212));
+        assert_eq!(meteo.wind_gust_direction, Some(214));
+        f32_equal_naive(meteo.air_temperature.unwrap(), -0.8);
+        assert_eq!(meteo.relative_humidity, Some(92));
+        assert_eq!(meteo.dew_point, None);
+        assert_eq!(meteo.air_pressure, Some(1032));
+        assert_eq!(meteo.pressure_tendency, Some(Tendency::Increasing));
+        assert_eq!(meteo.visibility, None);
+        assert_eq!(meteo.water_level, None);
+        assert_eq!(meteo.water_level_trend, None);
+        assert_eq!(
+            meteo.current_2,
+            Current {
+                speed: None,
+                direction: None,
+                depth: None,
+            }
+        );
+        assert_eq!(meteo.waves.height, None);
+        assert_eq!(meteo.sea_state, None);
+        assert_eq!(meteo.water_temperature, None);
+        assert_eq!(meteo.precipitation, None);
+        assert_eq!(meteo.salinity, None);
+        assert_eq!(meteo.ice, None);
+    }
+
+    #[test]
+    fn test_current() {
+        let meteo = decode(
+            b"8@2R5Ph0GhEa?1bGBviEOwvlFR06EuOwgqriwnSwe7wvlOwwsAwwnSGmwvwt",
+            0,
+        );
+        assert_eq!(meteo.format, MeteoHydroFormat::Current);
+        f32_equal_naive(meteo.longitude.unwrap(), 11.820666);
+        f32_equal_naive(meteo.latitude.unwrap(), 58.088917);
+        assert_eq!(meteo.fix_quality, Some(Accuracy::Dgps));
+        assert_eq!(meteo.day, Some(22));
+        assert_eq!(meteo.hour, Some(5));
+        assert_eq!(meteo.minute, Some(21));
+        assert_eq!(meteo.wind_speed, None);
+        assert_eq!(meteo.wind_gust, None);
+        assert_eq!(meteo.wind_direction, None);
+        assert_eq!(meteo.air_temperature, None);
+        assert_eq!(meteo.relative_humidity, None);
+        assert_eq!(meteo.dew_point, None);
+        assert_eq!(meteo.air_pressure, None);
+        assert_eq!(meteo.pressure_tendency, None);
+        assert_eq!(meteo.visibility, None);
+        assert!(!meteo.visibility_greater);
+        f32_equal_naive(meteo.water_level.unwrap(), -0.18);
+        assert_eq!(meteo.water_level_trend, Some(Tendency::Steady));
+        assert_eq!(meteo.surface_current.speed, None);
+        assert_eq!(meteo.current_3.depth, None);
+        assert_eq!(meteo.waves.height, None);
+        assert_eq!(meteo.swell.period, None);
+        assert_eq!(meteo.sea_state, None);
+        assert_eq!(meteo.water_temperature, None);
+        assert_eq!(meteo.precipitation, None);
+        assert_eq!(meteo.salinity, None);
+        assert_eq!(meteo.ice, None);
+    }
+}
@@ -0,0 +1,209 @@
+//! Application payloads carried by binary messages
+//!
+//! Binary messages (such as types 6 and 8) carry application-specific data,
+//! identified by a Designated Area Code (DAC) and Function ID (FID). DAC 1 is
+//! used for international applications, while other values are regional, and
+//! generally match the Maritime Identification Digits of the country.
+use crate::lib;
+
+pub mod area_notice;
+pub mod clearance_time_to_enter_port;
+pub mod dangerous_cargo_indication;
+pub mod environmental;
+pub mod inland;
+pub mod inland_eta;
+pub mod marine_traffic_signal;
+pub mod meteo_hydro;
+pub mod persons_on_board;
+pub mod route_information;
+pub mod text_description;
+
+use area_notice::AreaNotice;
+use clearance_time_to_enter_port::ClearanceTimeToEnterPort;
+use dangerous_cargo_indication::DangerousCargoIndication;
+use environmental::{parse_sensor_reports, SensorReportList};
+use inland::InlandStaticAndVoyageData;
+use inland_eta::EtaAtLockBridgeTerminal;
+use marine_traffic_signal::MarineTrafficSignal;
+use meteo_hydro::MeteoHydro;
+use persons_on_board::PersonsOnBoard;
+use route_information::RouteInformation;
+use text_description::TextDescription;
+
+#[cfg(all(not(feature = "std"), not(feature = "alloc")))]
+const MAX_DATA_SIZE_BYTES: usize = 119;
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub type PayloadData = lib::std::vec::Vec<u8>;
+#[cfg(all(not(feature = "std"), not(feature = "alloc")))]
+pub type PayloadData = lib::std::vec::Vec<u8, MAX_DATA_SIZE_BYTES>;
+
+/// Application payload of a binary message
+#[derive(Debug, PartialEq)]
+// Without an allocator, lists are stored inline, so some payloads are much larger
+#[cfg_attr(
+    all(not(feature = "std"), not(feature = "alloc")),
+    allow(clippy::large_enum_variant)
+)]
+pub enum BinaryPayload {
+    /// Area notice (DAC 1, FID 22 or 23)
+    AreaNotice(AreaNotice),
+    /// Clearance time to enter port (DAC 1, FID 18)
+    ClearanceTimeToEnterPort(ClearanceTimeToEnterPort),
+    /// Dangerous cargo indication (DAC 1, FID 25)
+    DangerousCargoIndication(DangerousCargoIndication),
+    /// ETA at lock/bridge/terminal (DAC 200, FID 21)
+    EtaAtLockBridgeTerminal(EtaAtLockBridgeTerminal),
+    /// Inland ship static and voyage related data (DAC 200, FID 10)
+    InlandStaticAndVoyageData(InlandStaticAndVoyageData),
+    /// Marine traffic signal (DAC 1, FID 19)
+    MarineTrafficSignal(MarineTrafficSignal),
+    /// Meteorological and hydrological data (DAC 1, FID 31, or the older FID 11)
+    MeteoHydro(MeteoHydro),
+    /// Number of persons on board (DAC 1, FID 16 or 40)
+    PersonsOnBoard(PersonsOnBoard),
+    /// Route information (DAC 1, FID 28)
+    RouteInformation(RouteInformation),
+    /// Environmental sensor reports (DAC 1, FID 26)
+    SensorReports(SensorReportList),
+    /// Text description (DAC 1, FID 29)
+    TextDescription(TextDescription),
+    /// Canadian regional application (DAC 316), as used in the St. Lawrence
+    /// Seaway and Great Lakes. The data is not decoded further.
+    Canadian { fid: u8, data: PayloadData },
+    /// An application not decoded by this library
+    Unknown {
+        dac: u16,
+        fid: u8,
+        data: PayloadData,
+    },
+}
+
+/// Decodes the application data of a binary message, given its DAC and FID. Data
+/// for applications that aren't recognized, or that fail to decode, is returned
+/// as `BinaryPayload::Unknown`.
+pub fn parse_binary(dac: u16, fid: u8, data: &[u8]) -> BinaryPayload {
+    let payload = match (dac, fid) {
+        (1, 11) => MeteoHydro::parse_legacy(data)
+            .map(BinaryPayload::MeteoHydro)
+            .ok(),
+        (1, 16 | 40) => PersonsOnBoard::parse(data)
+            .map(BinaryPayload::PersonsOnBoard)
+            .ok(),
+        (1, 18) => ClearanceTimeToEnterPort::parse(data)
+            .map(BinaryPayload::ClearanceTimeToEnterPort)
+            .ok(),
+        (1, 19) => MarineTrafficSignal::parse(data)
+            .map(BinaryPayload::MarineTrafficSignal)
+            .ok(),
+        (1, 25) => DangerousCargoIndication::parse(data)
+            .map(BinaryPayload::DangerousCargoIndication)
+            .ok(),
+        (1, 22 | 23) => AreaNotice::parse(data).map(BinaryPayload::AreaNotice).ok(),
+        (1, 26) => parse_sensor_reports(data)
+            .map(BinaryPayload::SensorReports)
+            .ok(),
+        (1, 28) => RouteInformation::parse(data)
+            .map(BinaryPayload::RouteInformation)
+            .ok(),
+        (1, 29) => TextDescription::parse(data)
+            .map(BinaryPayload::TextDescription)
+            .ok(),
+        (1, 31) => MeteoHydro::parse(data).map(BinaryPayload::MeteoHydro).ok(),
+        (200, 10) => InlandStaticAndVoyageData::parse(data)
+            .map(BinaryPayload::InlandStaticAndVoyageData)
+            .ok(),
+        (200, 21) => EtaAtLockBridgeTerminal::parse(data)
+            .map(BinaryPayload::EtaAtLockBridgeTerminal)
+            .ok(),
+        (316, _) => Some(BinaryPayload::Canadian {
+            fid,
+            data: payload_data(data),
+        }),
+        _ => None,
+    };
+    payload.unwrap_or_else(|| BinaryPayload::Unknown {
+        dac,
+        fid,
+        data: payload_data(data),
+    })
+}
+
+/// Longitude in 1/1000 minutes, with 181 degrees meaning not available
+fn parse_longitude_min_1000(data: i32) -> Option<f32> {
+    match data {
+        10_860_000 => None,
+        _ => Some(data as f32 / 60_000.0),
+    }
+}
+
+/// Latitude in 1/1000 minutes, with 91 degrees meaning not available
+fn parse_latitude_min_1000(data: i32) -> Option<f32> {
+    match data {
+        5_460_000 => None,
+        _ => Some(data as f32 / 60_000.0),
+    }
+}
+
+/// Hour of the day, with 24 meaning not available
+fn parse_hour_24(data: u8) -> Option<u8> {
+    match data {
+        24 => None,
+        _ => Some(data),
+    }
+}
+
+/// Copies raw application data into an owned buffer
+fn payload_data(data: &[u8]) -> PayloadData {
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    {
+        data.into()
+    }
+    #[cfg(all(not(feature = "std"), not(feature = "alloc")))]
+    {
+        data.iter().take(MAX_DATA_SIZE_BYTES).copied().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_canadian() {
+        let payload = parse_binary(316, 1, &[0x12, 0x34]);
+        assert_eq!(
+            payload,
+            BinaryPayload::Canadian {
+                fid: 1,
+                data: payload_data(&[0x12, 0x34]),
+            }
+        );
+    }
+
+    #[test]
+    fn test_truncated() {
+        let payload = parse_binary(1, 18, &[0x12, 0x34]);
+        assert_eq!(
+            payload,
+            BinaryPayload::Unknown {
+                dac: 1,
+                fid: 18,
+                data: payload_data(&[0x12, 0x34]),
+            }
+        );
+    }
+
+    #[test]
+    fn test_unknown() {
+        let payload = parse_binary(235, 10, &[0x44, 0x80]);
+        assert_eq!(
+            payload,
+            BinaryPayload::Unknown {
+                dac: 235,
+                fid: 10,
+                data: payload_data(&[0x44, 0x80]),
+            }
+        );
+    }
+}
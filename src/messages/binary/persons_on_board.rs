@@ -0,0 +1,110 @@
+//! Number of persons on board (IMO SN.1/Circ.289, DAC 1, FID 40; formerly IMO
+//! SN/Circ.236, DAC 1, FID 16)
+use crate::errors::Result;
+use nom::bits::{bits, complete::take as take_bits};
+use nom::combinator::map;
+use nom::IResult;
+
+/// Number of persons currently on board, including crew. This may be sent either
+/// addressed (type 6) or broadcast (type 8); the application data is the same, as
+/// is that of the older FID 16 it replaces.
+#[derive(Debug, PartialEq)]
+pub struct PersonsOnBoard {
+    /// Values of 8191 mean 8191 or more
+    pub persons: Option<u16>,
+}
+
+impl PersonsOnBoard {
+    pub fn parse(data: &[u8]) -> Result<Self> {
+        let (_, report) = parse_message(data)?;
+        Ok(report)
+    }
+}
+
+fn parse_persons(data: u16) -> Option<u16> {
+    match data {
+        0 => None,
+        _ => Some(data),
+    }
+}
+
+fn parse_message(data: &[u8]) -> IResult<&[u8], PersonsOnBoard> {
+    bits(move |data| -> IResult<_, _> {
+        let (data, persons) = map(take_bits(13u16), parse_persons)(data)?;
+        let (data, _spare) = take_bits::<_, u8, _, _>(3u8)(data)?;
+        Ok((data, PersonsOnBoard { persons }))
+    })(data)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unreadable_literal)]
+    use super::*;
+    use crate::messages::binary::BinaryPayload;
+    use crate::messages::binary_addressed::BinaryAddressedMessage;
+    use crate::messages::binary_broadcast_message::BinaryBroadcastMessage;
+    use crate::messages::bit_writer::BitWriter;
+    use crate::messages::AisMessageType;
+
+    #[test]
+    fn test_broadcast() {
+        let mut message = BitWriter::new();
+        message
+            .u32(8, 6)
+            .u32(0, 2)
+            .u32(366999712, 30)
+            .u32(0, 2)
+            .u32(1, 10)
+            .u32(40, 6);
+        message.u32(1234, 13).u32(0, 3);
+        let report = BinaryBroadcastMessage::parse(message.as_bytes()).unwrap();
+        assert_eq!(
+            report.decode_payload(),
+            BinaryPayload::PersonsOnBoard(PersonsOnBoard {
+                persons: Some(1234)
+            })
+        );
+    }
+
+    #[test]
+    fn test_addressed() {
+        let mut message = BitWriter::new();
+        message
+            .u32(6, 6)
+            .u32(0, 2)
+            .u32(2579999, 30)
+            .u32(1, 2)
+            .u32(366999712, 30)
+            .bool(false)
+            .u32(0, 1)
+            .u32(1, 10)
+            .u32(40, 6);
+        message.u32(0, 13).u32(0, 3);
+        let report = BinaryAddressedMessage::parse(message.as_bytes()).unwrap();
+        assert_eq!(
+            report.decode_payload(),
+            BinaryPayload::PersonsOnBoard(PersonsOnBoard { persons: None })
+        );
+    }
+
+    #[test]
+    fn test_fid_16() {
+        let mut message = BitWriter::new();
+        message
+            .u32(6, 6)
+            .u32(0, 2)
+            .u32(2579999, 30)
+            .u32(1, 2)
+            .u32(366999712, 30)
+            .bool(false)
+            .u32(0, 1)
+            .u32(1, 10)
+            .u32(16, 6);
+        message.u32(23, 13).u32(0, 3);
+        let report = BinaryAddressedMessage::parse(message.as_bytes()).unwrap();
+        assert_eq!(
+            report.decode_payload(),
+            BinaryPayload::PersonsOnBoard(PersonsOnBoard { persons: Some(23) })
+        );
+    }
+}
@@ -0,0 +1,272 @@
+//! Route information (IMO SN.1/Circ.289, DAC 1, FID 28)
+use super::super::navigation::{parse_latitude, parse_longitude};
+use super::super::parsers::*;
+use super::super::push_unwrap;
+use super::parse_hour_24;
+use crate::errors::Result;
+use crate::lib;
+use nom::bits::{bits, complete::take as take_bits};
+use nom::combinator::map;
+use nom::IResult;
+
+/// Maximum number of waypoints in a single message
+const MAX_WAYPOINTS: usize = 16;
+/// Width of a waypoint, in bits
+const WAYPOINT_BITS: usize = 55;
+
+/// Who is sending the route
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum SenderClass {
+    Ship,
+    Authority,
+    Reserved(u8),
+}
+
+impl SenderClass {
+    pub fn parse(data: u8) -> Self {
+        match data {
+            0 => Self::Ship,
+            1 => Self::Authority,
+            _ => Self::Reserved(data),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum RouteType {
+    Mandatory,
+    Recommended,
+    Alternative,
+    RecommendedThroughIce,
+    ShipRoutePlan,
+    /// Cancels the route with the same linkage ID
+    Cancellation,
+    Reserved(u8),
+}
+
+impl RouteType {
+    pub fn parse(data: u8) -> Option<Self> {
+        match data {
+            0 => None,
+            1 => Some(Self::Mandatory),
+            2 => Some(Self::Recommended),
+            3 => Some(Self::Alternative),
+            4 => Some(Self::RecommendedThroughIce),
+            5 => Some(Self::ShipRoutePlan),
+            31 => Some(Self::Cancellation),
+            _ => Some(Self::Reserved(data)),
+        }
+    }
+}
+
+/// A point along the route, in decimal degrees
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct Waypoint {
+    pub longitude: Option<f32>,
+    pub latitude: Option<f32>,
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub type WaypointList = lib::std::vec::Vec<Waypoint>;
+#[cfg(all(not(feature = "std"), not(feature = "alloc")))]
+pub type WaypointList = lib::std::vec::Vec<Waypoint, MAX_WAYPOINTS>;
+
+/// Route to be followed, or planned, by a vessel, sent addressed (type 6) either by
+/// an authority to a ship, or between ships
+#[derive(Debug, PartialEq)]
+pub struct RouteInformation {
+    /// Links this message to related messages
+    pub linkage_id: u16,
+    pub sender_class: SenderClass,
+    pub route_type: Option<RouteType>,
+    /// UTC month of the start of validity
+    pub start_month: Option<u8>,
+    pub start_day: Option<u8>,
+    pub start_hour: Option<u8>,
+    pub start_minute: Option<u8>,
+    /// Minutes the route is valid for, from the start time. 0 cancels the route;
+    /// `None` means the validity isn't limited.
+    pub duration: Option<u32>,
+    pub waypoints: WaypointList,
+}
+
+impl RouteInformation {
+    pub fn parse(data: &[u8]) -> Result<Self> {
+        let (_, report) = parse_message(data)?;
+        Ok(report)
+    }
+}
+
+fn parse_duration(data: u32) -> Option<u32> {
+    match data {
+        262_143 => None,
+        _ => Some(data),
+    }
+}
+
+fn parse_waypoint(data: (&[u8], usize)) -> IResult<(&[u8], usize), Waypoint> {
+    let (data, longitude) = map(|data| signed_i32(data, 28), parse_longitude)(data)?;
+    let (data, latitude) = map(|data| signed_i32(data, 27), parse_latitude)(data)?;
+    Ok((
+        data,
+        Waypoint {
+            longitude,
+            latitude,
+        },
+    ))
+}
+
+fn parse_message(data: &[u8]) -> IResult<&[u8], RouteInformation> {
+    bits(move |data| -> IResult<_, _> {
+        let (data, linkage_id) = take_bits(10u16)(data)?;
+        let (data, sender_class) = map(take_bits(3u8), SenderClass::parse)(data)?;
+        let (data, route_type) = map(take_bits(5u8), RouteType::parse)(data)?;
+        let (data, start_month) = parse_month(data)?;
+        let (data, start_day) = parse_day(data)?;
+        let (data, start_hour) = map(parse_hour, parse_hour_24)(data)?;
+        let (data, start_minute) = parse_minsec(data)?;
+        let (data, duration) = map(take_bits(18u32), parse_duration)(data)?;
+        // Waypoints past the count are padding; a count larger than the message
+        // has room for is cut short rather than rejected
+        let (mut data, waypoint_count) = take_bits::<_, u8, _, _>(5u8)(data)?;
+        let waypoint_count = lib::std::cmp::min(waypoint_count as usize, MAX_WAYPOINTS);
+        let mut waypoints = WaypointList::default();
+        while waypoints.len() < waypoint_count && remaining_bits(data) >= WAYPOINT_BITS {
+            let (remaining, waypoint) = parse_waypoint(data)?;
+            push_unwrap(&mut waypoints, waypoint);
+            data = remaining;
+        }
+        Ok((
+            data,
+            RouteInformation {
+                linkage_id,
+                sender_class,
+                route_type,
+                start_month,
+                start_day,
+                start_hour,
+                start_minute,
+                duration,
+                waypoints,
+            },
+        ))
+    })(data)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unreadable_literal)]
+    use super::*;
+    use crate::messages::binary::BinaryPayload;
+    use crate::messages::binary_addressed::BinaryAddressedMessage;
+    use crate::messages::bit_writer::BitWriter;
+    use crate::messages::AisMessageType;
+    use crate::test_helpers::*;
+
+    #[test]
+    fn test_route_information() {
+        let mut message = BitWriter::new();
+        message
+            .u32(6, 6)
+            .u32(0, 2)
+            .u32(2579999, 30)
+            .u32(0, 2)
+            .u32(257855600, 30)
+            .bool(false)
+            .bool(false)
+            .u32(1, 10)
+            .u32(28, 6);
+        message
+            .u32(42, 10)
+            .u32(1, 3)
+            .u32(2, 5)
+            .u32(7, 4)
+            .u32(14, 5)
+            .u32(9, 5)
+            .u32(30, 6)
+            .u32(120, 18)
+            .u32(2, 5)
+            .i32(3_000_000, 28)
+            .i32(36_000_000, 27)
+            .i32(-1_800_000, 28)
+            .i32(-600_000, 27);
+        let report = BinaryAddressedMessage::parse(message.as_bytes()).unwrap();
+        let route = match report.decode_payload() {
+            BinaryPayload::RouteInformation(route) => route,
+            other => panic!("Expected route information, got {:?}", other),
+        };
+        assert_eq!(route.linkage_id, 42);
+        assert_eq!(route.sender_class, SenderClass::Authority);
+        assert_eq!(route.route_type, Some(RouteType::Recommended));
+        assert_eq!(route.start_month, Some(7));
+        assert_eq!(route.start_day, Some(14));
+        assert_eq!(route.start_hour, Some(9));
+        assert_eq!(route.start_minute, Some(30));
+        assert_eq!(route.duration, Some(120));
+        assert_eq!(route.waypoints.len(), 2);
+        f32_equal_naive(route.waypoints[0].longitude.unwrap(), 5.0);
+        f32_equal_naive(route.waypoints[0].latitude.unwrap(), 60.0);
+        f32_equal_naive(route.waypoints[1].longitude.unwrap(), -3.0);
+        f32_equal_naive(route.waypoints[1].latitude.unwrap(), -1.0);
+    }
+
+    #[test]
+    fn test_waypoint_count() {
+        let mut message = BitWriter::new();
+        message
+            .u32(0, 10)
+            .u32(0, 3)
+            .u32(1, 5)
+            .u32(0, 4)
+            .u32(0, 5)
+            .u32(24, 5)
+            .u32(60, 6)
+            .u32(262143, 18)
+            .u32(1, 5)
+            .i32(3_000_000, 28)
+            .i32(36_000_000, 27)
+            .u32(0, 28)
+            .u32(0, 27);
+        let route = RouteInformation::parse(message.as_bytes()).unwrap();
+        // The second waypoint's worth of bits is past the count, so is padding
+        assert_eq!(route.waypoints.len(), 1);
+        f32_equal_naive(route.waypoints[0].longitude.unwrap(), 5.0);
+
+        let mut message = BitWriter::new();
+        message
+            .u32(0, 10)
+            .u32(0, 3)
+            .u32(1, 5)
+            .u32(0, 4)
+            .u32(0, 5)
+            .u32(24, 5)
+            .u32(60, 6)
+            .u32(262143, 18)
+            .u32(3, 5)
+            .i32(3_000_000, 28)
+            .i32(36_000_000, 27);
+        let route = RouteInformation::parse(message.as_bytes()).unwrap();
+        // Only one of the three waypoints counted is present
+        assert_eq!(route.waypoints.len(), 1);
+    }
+
+    #[test]
+    fn test_no_waypoints() {
+        let mut message = BitWriter::new();
+        message
+            .u32(0, 10)
+            .u32(0, 3)
+            .u32(31, 5)
+            .u32(0, 4)
+            .u32(0, 5)
+            .u32(24, 5)
+            .u32(60, 6)
+            .u32(262143, 18)
+            .u32(0, 5);
+        let route = RouteInformation::parse(message.as_bytes()).unwrap();
+        assert_eq!(route.route_type, Some(RouteType::Cancellation));
+        assert_eq!(route.start_hour, None);
+        assert_eq!(route.duration, None);
+        assert!(route.waypoints.is_empty());
+    }
+}
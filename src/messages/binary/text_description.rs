@@ -0,0 +1,68 @@
+//! Text description (IMO SN.1/Circ.289, DAC 1, FID 29)
+use super::super::parsers::*;
+use crate::errors::Result;
+use nom::bits::{bits, complete::take as take_bits};
+use nom::IResult;
+
+/// Free text, broadcast (type 8), usually supplementing other messages such as
+/// area notices, to which it is tied by the linkage ID
+#[derive(Debug, PartialEq)]
+pub struct TextDescription {
+    /// Links this message to related messages
+    pub linkage_id: u16,
+    pub text: AsciiString,
+}
+
+impl TextDescription {
+    pub fn parse(data: &[u8]) -> Result<Self> {
+        let (_, report) = parse_message(data)?;
+        Ok(report)
+    }
+}
+
+fn parse_message(data: &[u8]) -> IResult<&[u8], TextDescription> {
+    bits(move |data| -> IResult<_, _> {
+        let (data, linkage_id) = take_bits(10u16)(data)?;
+        let (data, text) = parse_6bit_ascii(data, remaining_bits(data))?;
+        Ok((data, TextDescription { linkage_id, text }))
+    })(data)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unreadable_literal)]
+    use super::*;
+    use crate::messages::binary::BinaryPayload;
+    use crate::messages::binary_broadcast_message::BinaryBroadcastMessage;
+    use crate::messages::bit_writer::BitWriter;
+    use crate::messages::AisMessageType;
+
+    #[test]
+    fn test_text_description() {
+        let mut message = BitWriter::new();
+        message
+            .u32(8, 6)
+            .u32(0, 2)
+            .u32(3669987, 30)
+            .u32(0, 2)
+            .u32(1, 10)
+            .u32(29, 6);
+        message.u32(42, 10).string6("DREDGING IN CHANNEL", 114);
+        let report = BinaryBroadcastMessage::parse(message.as_bytes()).unwrap();
+        let description = match report.decode_payload() {
+            BinaryPayload::TextDescription(description) => description,
+            other => panic!("Expected text description, got {:?}", other),
+        };
+        assert_eq!(description.linkage_id, 42);
+        assert_eq!(description.text, "DREDGING IN CHANNEL");
+    }
+
+    #[test]
+    fn test_empty_text() {
+        let mut message = BitWriter::new();
+        message.u32(7, 10).u32(0, 6);
+        let description = TextDescription::parse(message.as_bytes()).unwrap();
+        assert_eq!(description.linkage_id, 7);
+        assert_eq!(description.text, "");
+    }
+}
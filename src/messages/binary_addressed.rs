@@ -1,4 +1,5 @@
 //! Binary Addressed Message (type 6)
+use super::binary::{parse_binary, BinaryPayload};
 use super::parsers::u8_to_bool;
 use super::AisMessageType;
 use crate::errors::Result;
@@ -28,6 +29,13 @@ pub struct BinaryAddressedMessage {
     pub data: MessageData,
 }
 
+impl BinaryAddressedMessage {
+    /// Decodes the application data carried by this message, based on its DAC and FID
+    pub fn decode_payload(&self) -> BinaryPayload {
+        parse_binary(self.dac, self.fid, &self.data)
+    }
+}
+
 impl<'a> AisMessageType<'a> for BinaryAddressedMessage {
     fn name(&self) -> &'static str {
         "Binary Addressed Message"
@@ -1,4 +1,5 @@
 //! Binary Broadcast Message (type 8)
+use super::binary::{parse_binary, BinaryPayload};
 use super::AisMessageType;
 use crate::errors::Result;
 use crate::lib;
@@ -25,6 +26,13 @@ pub struct BinaryBroadcastMessage {
     pub data: MessageData,
 }
 
+impl BinaryBroadcastMessage {
+    /// Decodes the application data carried by this message, based on its DAC and FID
+    pub fn decode_payload(&self) -> BinaryPayload {
+        parse_binary(self.dac, self.fid, &self.data)
+    }
+}
+
 impl<'a> AisMessageType<'a> for BinaryBroadcastMessage {
     fn name(&self) -> &'static str {
         "Binary Broadcast Message"
@@ -131,4 +139,54 @@ mod tests {
         assert_eq!(report.dac, 1);
         assert_eq!(report.fid, 31);
     }
+
+    #[test]
+    fn test_unimplemented_application() {
+        use crate::messages::bit_writer::BitWriter;
+
+        // VTS-generated targets
+        let mut message = BitWriter::new();
+        message
+            .u32(8, 6)
+            .u32(0, 2)
+            .u32(2655619, 30)
+            .u32(0, 2)
+            .u32(1, 10)
+            .u32(17, 6)
+            .u32(0xcafe, 16);
+        let report = BinaryBroadcastMessage::parse(message.as_bytes()).unwrap();
+        match report.decode_payload() {
+            BinaryPayload::Unknown { dac, fid, data } => {
+                assert_eq!(dac, 1);
+                assert_eq!(fid, 17);
+                assert_eq!(data, report.data);
+            }
+            other => panic!("Expected an unknown payload, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_canadian_regional() {
+        use crate::messages::bit_writer::BitWriter;
+
+        let mut message = BitWriter::new();
+        message
+            .u32(8, 6)
+            .u32(0, 2)
+            .u32(316001234, 30)
+            .u32(0, 2)
+            .u32(316, 10)
+            .u32(1, 6)
+            .u32(0xdeadbeef, 32);
+        let report = BinaryBroadcastMessage::parse(message.as_bytes()).unwrap();
+        assert_eq!(report.dac, 316);
+        assert_eq!(report.fid, 1);
+        match report.decode_payload() {
+            BinaryPayload::Canadian { fid, data } => {
+                assert_eq!(fid, 1);
+                assert_eq!(&data[..], [0xde, 0xad, 0xbe, 0xef]);
+            }
+            other => panic!("Expected Canadian regional payload, got {:?}", other),
+        }
+    }
 }
@@ -0,0 +1,144 @@
+//! Bit-level writer, for building AIS payloads
+use super::push_unwrap;
+use crate::sentence::AisRawData;
+
+/// Packs values into a bitstream, most significant bit first, using the same layout
+/// the message parsers read. This makes it possible to build custom payloads, such
+/// as application data for binary messages.
+///
+/// # Example
+/// ```
+/// use ais::messages::bit_writer::BitWriter;
+/// use ais::messages::binary_broadcast_message::BinaryBroadcastMessage;
+/// use ais::messages::AisMessageType;
+///
+/// // Application payload
+/// let mut payload = BitWriter::new();
+/// payload.u32(42, 8).i32(-5, 8).string6("AIS", 18).u32(0, 6);
+/// assert_eq!(payload.bit_len(), 40);
+///
+/// // Type 8 message wrapping it, with DAC 1 and FID 29
+/// let mut message = BitWriter::new();
+/// message.u32(8, 6).u32(0, 2).u32(366123456, 30).u32(0, 2).u32(1, 10).u32(29, 6);
+/// for byte in payload.as_bytes() {
+///     message.u32(*byte as u32, 8);
+/// }
+///
+/// let report = BinaryBroadcastMessage::parse(message.as_bytes())?;
+/// assert_eq!(report.mmsi, 366123456);
+/// assert_eq!(report.dac, 1);
+/// assert_eq!(report.fid, 29);
+/// assert_eq!(&report.data[..], payload.as_bytes());
+/// # Ok::<(), ais::errors::Error>(())
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct BitWriter {
+    data: AisRawData,
+    bit_len: usize,
+}
+
+impl BitWriter {
+    /// Creates a new, empty `BitWriter`
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Writes the lowest `bits` bits of `value`
+    pub fn u32(&mut self, value: u32, bits: usize) -> &mut Self {
+        assert!(bits <= 32);
+        for shift in (0..bits).rev() {
+            self.bit((value >> shift) & 1 == 1);
+        }
+        self
+    }
+
+    /// Writes `value` as a two's complement number, `bits` wide
+    pub fn i32(&mut self, value: i32, bits: usize) -> &mut Self {
+        self.u32(value as u32, bits)
+    }
+
+    /// Writes a single bit flag
+    pub fn bool(&mut self, value: bool) -> &mut Self {
+        self.bit(value);
+        self
+    }
+
+    /// Writes `text` as 6-bit ASCII, in a field `bits` wide. Shorter text is padded
+    /// with `@`, and longer text is truncated. Lowercase letters are converted to
+    /// uppercase; other characters outside the 6-bit character set are written as `?`.
+    pub fn string6(&mut self, text: &str, bits: usize) -> &mut Self {
+        let mut chars = text.bytes();
+        for _ in 0..bits / 6 {
+            let symbol = chars.next().map(ascii_to_sixbit).unwrap_or(0);
+            self.u32(symbol as u32, 6);
+        }
+        self.u32(0, bits % 6)
+    }
+
+    /// Number of bits written so far
+    pub fn bit_len(&self) -> usize {
+        self.bit_len
+    }
+
+    /// The bitstream written so far. Any unused bits in the final byte are zero.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Consumes the writer, returning the bitstream
+    pub fn into_bytes(self) -> AisRawData {
+        self.data
+    }
+
+    fn bit(&mut self, value: bool) {
+        if self.bit_len.is_multiple_of(8) {
+            push_unwrap(&mut self.data, 0);
+        }
+        if value {
+            self.data[self.bit_len / 8] |= 0x80 >> (self.bit_len % 8);
+        }
+        self.bit_len += 1;
+    }
+}
+
+/// Converts an ASCII character to its 6-bit representation
+#[inline]
+fn ascii_to_sixbit(data: u8) -> u8 {
+    match data.to_ascii_uppercase() {
+        upper @ 64..=95 => upper - 64,
+        32..=63 => data,
+        _ => b'?',
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::messages::parsers::{parse_6bit_ascii, signed_i32};
+    use nom::bits::complete::take as take_bits;
+
+    #[test]
+    fn write_unaligned() {
+        let mut writer = BitWriter::new();
+        writer.u32(0b101, 3).bool(true).u32(0x1ff, 9);
+        assert_eq!(writer.bit_len(), 13);
+        assert_eq!(writer.as_bytes(), [0b1011_1111, 0b1111_1000]);
+    }
+
+    #[test]
+    fn write_negative() {
+        let mut writer = BitWriter::new();
+        writer.i32(-1234, 18);
+        let (_, value) = signed_i32((writer.as_bytes(), 0), 18).unwrap();
+        assert_eq!(value, -1234);
+    }
+
+    #[test]
+    fn write_string() {
+        let mut writer = BitWriter::new();
+        writer.u32(3, 2).string6("Sf Bay", 42);
+        let (data, _) = take_bits::<_, u8, _, ()>(2u8)((writer.as_bytes(), 0)).unwrap();
+        let (_, text) = parse_6bit_ascii(data, 42).unwrap();
+        assert_eq!(text, "SF BAY");
+    }
+}
@@ -0,0 +1,266 @@
+//! Channel Management (type 22)
+//!
+//! See message 22 in [ITU-R M.1371](https://www.itu.int/rec/R-REC-M.1371), and
+//! <https://gpsd.gitlab.io/gpsd/AIVDM.html#_type_22_channel_management>.
+use super::group_assignment_command::TransmitMode;
+use super::navigation::*;
+use super::parsers::*;
+use super::AisMessageType;
+use crate::errors::Result;
+use nom::bits::{bits, complete::take as take_bits};
+use nom::combinator::map;
+use nom::IResult;
+
+/// Transmit power that stations in the region should use
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum TxPower {
+    /// The station's default, high power setting
+    High,
+    /// Low power, as used in busy areas, to reduce range
+    Low,
+}
+
+impl TxPower {
+    pub fn parse(data: u8) -> Self {
+        match data {
+            0 => Self::High,
+            _ => Self::Low,
+        }
+    }
+}
+
+/// Bandwidth of an assigned channel
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum ChannelBandwidth {
+    /// As specified by the channel number
+    Default,
+    /// 12.5 kHz. Later editions of ITU-R M.1371 no longer use narrow channels, and
+    /// treat this value as spare.
+    Narrow,
+}
+
+impl ChannelBandwidth {
+    pub fn parse(data: u8) -> Self {
+        match data {
+            0 => Self::Default,
+            _ => Self::Narrow,
+        }
+    }
+}
+
+/// Sent by base stations to assign the AIS channels, and related settings, used by
+/// stations in a region. The region is either given as a rectangle, or the message
+/// is addressed to up to two stations.
+#[derive(Debug, PartialEq)]
+pub struct ChannelManagement {
+    pub message_type: u8,
+    pub repeat_indicator: u8,
+    pub mmsi: u32,
+    /// ITU-R M.1084 channel number
+    pub channel_a: u16,
+    /// ITU-R M.1084 channel number
+    pub channel_b: u16,
+    pub tx_rx_mode: TransmitMode,
+    pub power: TxPower,
+    pub ne_longitude: Option<f32>,
+    pub ne_latitude: Option<f32>,
+    pub sw_longitude: Option<f32>,
+    pub sw_latitude: Option<f32>,
+    pub dest1_mmsi: Option<u32>,
+    pub dest2_mmsi: Option<u32>,
+    /// Whether this message is addressed to `dest1_mmsi` and `dest2_mmsi`, rather
+    /// than broadcast to the area bounded by the NE and SW corners
+    pub addressed: bool,
+    pub band_a: ChannelBandwidth,
+    pub band_b: ChannelBandwidth,
+    /// Size of the transitional zone, in nautical miles, less one; see
+    /// [`zone_size_nm()`](#method.zone_size_nm)
+    pub zone_size: u8,
+}
+
+impl ChannelManagement {
+    /// Transmit power to use in the region
+    pub fn tx_power(&self) -> TxPower {
+        self.power
+    }
+
+    /// Bandwidth of `channel_a`
+    pub fn bandwidth_a(&self) -> ChannelBandwidth {
+        self.band_a
+    }
+
+    /// Bandwidth of `channel_b`
+    pub fn bandwidth_b(&self) -> ChannelBandwidth {
+        self.band_b
+    }
+
+    /// Size of the transitional zone around the region, in nautical miles, from 1
+    /// to 8. Within it, stations switch over to the region's channels.
+    pub fn zone_size_nm(&self) -> u8 {
+        self.zone_size + 1
+    }
+}
+
+impl<'a> AisMessageType<'a> for ChannelManagement {
+    fn name(&self) -> &'static str {
+        "Channel Management"
+    }
+
+    fn parse(data: &[u8]) -> Result<Self> {
+        let (_, report) = parse_base(data)?;
+        Ok(report)
+    }
+}
+
+/// Modes 4-15 are reserved
+fn parse_tx_rx_mode(data: u8) -> TransmitMode {
+    match data {
+        0..=3 => data.into(),
+        _ => TransmitMode::Reserved,
+    }
+}
+
+type Area = (Option<f32>, Option<f32>, Option<f32>, Option<f32>);
+
+fn parse_area(data: (&[u8], usize)) -> IResult<(&[u8], usize), Area> {
+    let (data, ne_longitude) = map(|data| signed_i32(data, 18), parse_longitude_min_10)(data)?;
+    let (data, ne_latitude) = map(|data| signed_i32(data, 17), parse_latitude_min_10)(data)?;
+    let (data, sw_longitude) = map(|data| signed_i32(data, 18), parse_longitude_min_10)(data)?;
+    let (data, sw_latitude) = map(|data| signed_i32(data, 17), parse_latitude_min_10)(data)?;
+    Ok((data, (ne_longitude, ne_latitude, sw_longitude, sw_latitude)))
+}
+
+fn parse_destinations(data: (&[u8], usize)) -> IResult<(&[u8], usize), (u32, u32)> {
+    let (data, dest1_mmsi) = take_bits(30u32)(data)?;
+    let (data, _spare) = take_bits::<_, u8, _, _>(5u8)(data)?;
+    let (data, dest2_mmsi) = take_bits(30u32)(data)?;
+    let (data, _spare) = take_bits::<_, u8, _, _>(5u8)(data)?;
+    Ok((data, (dest1_mmsi, dest2_mmsi)))
+}
+
+fn parse_base(data: &[u8]) -> IResult<&[u8], ChannelManagement> {
+    bits(move |data| -> IResult<_, _> {
+        let (data, message_type) = take_bits(6u8)(data)?;
+        let (data, repeat_indicator) = take_bits(2u8)(data)?;
+        let (data, mmsi) = take_bits(30u32)(data)?;
+        let (data, _spare) = take_bits::<_, u8, _, _>(2u8)(data)?;
+        let (data, channel_a) = take_bits(12u16)(data)?;
+        let (data, channel_b) = take_bits(12u16)(data)?;
+        let (data, tx_rx_mode) = map(take_bits(4u8), parse_tx_rx_mode)(data)?;
+        let (data, power) = map(take_bits(1u8), TxPower::parse)(data)?;
+        // The next 70 bits are either an area or two MMSIs, depending on the
+        // addressed flag that follows them
+        let destination = data;
+        let (data, _) = take_bits::<_, u64, _, _>(35u8)(data)?;
+        let (data, _) = take_bits::<_, u64, _, _>(35u8)(data)?;
+        let (data, addressed) = map(take_bits(1u8), u8_to_bool)(data)?;
+        let (data, band_a) = map(take_bits(1u8), ChannelBandwidth::parse)(data)?;
+        let (data, band_b) = map(take_bits(1u8), ChannelBandwidth::parse)(data)?;
+        let (data, zone_size) = take_bits(3u8)(data)?;
+        let (data, _spare) = take_bits::<_, u32, _, _>(23u8)(data)?;
+        let mut report = ChannelManagement {
+            message_type,
+            repeat_indicator,
+            mmsi,
+            channel_a,
+            channel_b,
+            tx_rx_mode,
+            power,
+            ne_longitude: None,
+            ne_latitude: None,
+            sw_longitude: None,
+            sw_latitude: None,
+            dest1_mmsi: None,
+            dest2_mmsi: None,
+            addressed,
+            band_a,
+            band_b,
+            zone_size,
+        };
+        if addressed {
+            let (_, (dest1_mmsi, dest2_mmsi)) = parse_destinations(destination)?;
+            report.dest1_mmsi = Some(dest1_mmsi);
+            report.dest2_mmsi = Some(dest2_mmsi);
+        } else {
+            let (_, (ne_longitude, ne_latitude, sw_longitude, sw_latitude)) =
+                parse_area(destination)?;
+            report.ne_longitude = ne_longitude;
+            report.ne_latitude = ne_latitude;
+            report.sw_longitude = sw_longitude;
+            report.sw_latitude = sw_latitude;
+        }
+        Ok((data, report))
+    })(data)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unreadable_literal)]
+    use super::*;
+    use crate::messages::bit_writer::BitWriter;
+    use crate::test_helpers::*;
+
+    #[test]
+    fn test_broadcast() {
+        let bytestream = b"F030p?j2N2P73FiiNesU3FR10000";
+        let bitstream = crate::messages::unarmor(bytestream, 0).unwrap();
+        let report = ChannelManagement::parse(bitstream.as_ref()).unwrap();
+        assert_eq!(report.message_type, 22);
+        assert_eq!(report.repeat_indicator, 0);
+        assert_eq!(report.mmsi, 3160127);
+        assert_eq!(report.channel_a, 2087);
+        assert_eq!(report.channel_b, 2088);
+        assert_eq!(report.tx_rx_mode, TransmitMode::TxATxBRxARxB);
+        assert_eq!(report.power, TxPower::High);
+        assert_eq!(report.tx_power(), TxPower::High);
+        assert!(!report.addressed);
+        f32_equal_naive(report.ne_longitude.unwrap(), -51.75);
+        f32_equal_naive(report.ne_latitude.unwrap(), 48.416668);
+        f32_equal_naive(report.sw_longitude.unwrap(), -56.5);
+        f32_equal_naive(report.sw_latitude.unwrap(), 45.766666);
+        assert_eq!(report.dest1_mmsi, None);
+        assert_eq!(report.dest2_mmsi, None);
+        assert_eq!(report.band_a, ChannelBandwidth::Default);
+        assert_eq!(report.band_b, ChannelBandwidth::Default);
+        assert_eq!(report.zone_size, 2);
+        assert_eq!(report.zone_size_nm(), 3);
+    }
+
+    #[test]
+    fn test_addressed() {
+        let mut message = BitWriter::new();
+        message
+            .u32(22, 6)
+            .u32(0, 2)
+            .u32(3669145, 30)
+            .u32(0, 2)
+            .u32(2087, 12)
+            .u32(2088, 12)
+            .u32(1, 4)
+            .bool(true)
+            .u32(366999712, 30)
+            .u32(0, 5)
+            .u32(367625810, 30)
+            .u32(0, 5)
+            .bool(true)
+            .bool(false)
+            .bool(true)
+            .u32(4, 3)
+            .u32(0, 23);
+        let report = ChannelManagement::parse(message.as_bytes()).unwrap();
+        assert_eq!(report.mmsi, 3669145);
+        assert_eq!(report.tx_rx_mode, TransmitMode::TxARxARxB);
+        assert_eq!(report.power, TxPower::Low);
+        assert!(report.addressed);
+        assert_eq!(report.dest1_mmsi, Some(366999712));
+        assert_eq!(report.dest2_mmsi, Some(367625810));
+        assert_eq!(report.ne_longitude, None);
+        assert_eq!(report.sw_latitude, None);
+        assert_eq!(report.band_a, ChannelBandwidth::Default);
+        assert_eq!(report.band_b, ChannelBandwidth::Narrow);
+        assert_eq!(report.tx_power(), TxPower::Low);
+        assert_eq!(report.bandwidth_a(), ChannelBandwidth::Default);
+        assert_eq!(report.bandwidth_b(), ChannelBandwidth::Narrow);
+        assert_eq!(report.zone_size, 4);
+    }
+}
@@ -0,0 +1,106 @@
+//! Messages decoded by handlers registered with an
+//! [`AisParser`](../../sentence/struct.AisParser.html), for message types this crate
+//! doesn't decode, or to replace its own decoding of a type
+use crate::errors::Result;
+use crate::lib;
+use lib::std::any::Any;
+use lib::std::boxed::Box;
+use lib::std::fmt;
+use nom::bits::{bits, complete::take as take_bits};
+use nom::IResult;
+
+/// Decodes an unarmored message; see
+/// [`AisParser::register_handler()`](../../sentence/struct.AisParser.html#method.register_handler)
+pub type MessageHandler = Box<dyn Fn(&[u8]) -> Result<CustomMessage> + Send + Sync>;
+
+/// Message decoded by a registered handler. The report can be of any type, and is
+/// retrieved with [`downcast_ref()`](#method.downcast_ref).
+pub struct CustomMessage {
+    message_type: u8,
+    mmsi: u32,
+    report: Box<dyn Any + Send + Sync>,
+}
+
+impl CustomMessage {
+    /// Wraps the report decoded by a handler. The message type and MMSI are filled
+    /// in from the message header by the parser.
+    pub fn new<T: Any + Send + Sync>(report: T) -> Self {
+        Self {
+            message_type: 0,
+            mmsi: 0,
+            report: Box::new(report),
+        }
+    }
+
+    pub fn message_type(&self) -> u8 {
+        self.message_type
+    }
+
+    /// MMSI of the station that sent this message
+    pub fn mmsi(&self) -> u32 {
+        self.mmsi
+    }
+
+    /// The report returned by the handler, if it is a `T`
+    pub fn downcast_ref<T: Any>(&self) -> Option<&T> {
+        self.report.downcast_ref()
+    }
+
+    pub(crate) fn report(&self) -> &dyn Any {
+        &*self.report
+    }
+}
+
+impl fmt::Debug for CustomMessage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CustomMessage")
+            .field("message_type", &self.message_type)
+            .field("mmsi", &self.mmsi)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Reports can't be compared, so a custom message is only equal to itself
+impl PartialEq for CustomMessage {
+    fn eq(&self, other: &Self) -> bool {
+        core::ptr::eq(self, other)
+    }
+}
+
+/// Calls `handler` on an unarmored message, and fills in the header fields
+pub(crate) fn decode(handler: &MessageHandler, data: &[u8]) -> Result<CustomMessage> {
+    let (_, (message_type, mmsi)) = parse_header(data)?;
+    let mut message = handler(data)?;
+    message.message_type = message_type;
+    message.mmsi = mmsi;
+    Ok(message)
+}
+
+fn parse_header(data: &[u8]) -> IResult<&[u8], (u8, u32)> {
+    bits(move |data| -> IResult<_, _> {
+        let (data, message_type) = take_bits(6u8)(data)?;
+        let (data, _repeat_indicator) = take_bits::<_, u8, _, _>(2u8)(data)?;
+        let (data, mmsi) = take_bits(30u32)(data)?;
+        Ok((data, (message_type, mmsi)))
+    })(data)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unreadable_literal)]
+    use super::*;
+    use crate::messages::bit_writer::BitWriter;
+
+    #[test]
+    fn decode_header() {
+        let handler: MessageHandler = Box::new(|data: &[u8]| Ok(CustomMessage::new(data.len())));
+        let mut message = BitWriter::new();
+        message.u32(28, 6).u32(0, 2).u32(366999712, 30).u32(0, 2);
+        let decoded = decode(&handler, message.as_bytes()).unwrap();
+        assert_eq!(decoded.message_type(), 28);
+        assert_eq!(decoded.mmsi(), 366999712);
+        assert_eq!(decoded.downcast_ref::<usize>(), Some(&5));
+        assert_eq!(decoded.downcast_ref::<u8>(), None);
+        assert!(decode(&handler, &[28 << 2]).is_err());
+    }
+}
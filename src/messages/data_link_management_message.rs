@@ -46,6 +46,9 @@ pub struct DataLinkManagementMessage {
     pub repeat_indicator: u8,
     pub mmsi: u32,
     pub reservations: SlotReservationList,
+    /// Set if any non-zero bits follow a fourth reservation. At most four are
+    /// allowed, so anything after them should be zero padding, and is ignored.
+    pub trailing_data: bool,
 }
 
 impl<'a> AisMessageType<'a> for DataLinkManagementMessage {
@@ -69,6 +72,7 @@ fn parse_base<'a>(data: &'a [u8]) -> IResult<&'a [u8], DataLinkManagementMessage
         let (data, reservations) = many_m_n(1, 4, SlotReservation::parse)(data)?;
         #[cfg(all(not(feature = "std"), not(feature = "alloc")))]
         let (data, reservations) = many_m_n::<_, _, _, _, 4>(1, SlotReservation::parse)(data)?;
+        let trailing_data = reservations.len() == 4 && has_nonzero_bits(data);
         Ok((
             data,
             DataLinkManagementMessage {
@@ -76,15 +80,24 @@ fn parse_base<'a>(data: &'a [u8]) -> IResult<&'a [u8], DataLinkManagementMessage
                 repeat_indicator,
                 mmsi,
                 reservations,
+                trailing_data,
             },
         ))
     })(data)
 }
 
+fn has_nonzero_bits(data: (&[u8], usize)) -> bool {
+    match data.0.split_first() {
+        Some((first, rest)) => first & (0xff >> data.1) != 0 || rest.iter().any(|byte| *byte != 0),
+        None => false,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     #![allow(clippy::unreadable_literal)]
     use super::*;
+    use crate::messages::bit_writer::BitWriter;
 
     #[test]
     fn test_2_slots() {
@@ -97,6 +110,7 @@ mod tests {
         assert_eq!(report.reservations.len(), 2);
         assert_eq!(report.reservations[0].num_slots, 1);
         assert_eq!(report.reservations[1].increment, 1125);
+        assert!(!report.trailing_data);
     }
 
     #[test]
@@ -111,4 +125,25 @@ mod tests {
         assert_eq!(report.reservations[1].num_slots, 1);
         assert_eq!(report.reservations[2].increment, 1125);
     }
+
+    #[test]
+    fn test_trailing_data() {
+        let mut message = BitWriter::new();
+        message.u32(20, 6).u32(0, 2).u32(2288236, 30).u32(0, 2);
+        for offset in 1..=4 {
+            message
+                .u32(offset * 100, 12)
+                .u32(1, 4)
+                .u32(7, 3)
+                .u32(1125, 11);
+        }
+        message.u32(0b101, 3).u32(0, 5);
+        let report = DataLinkManagementMessage::parse(message.as_bytes()).unwrap();
+        assert_eq!(report.reservations.len(), 4);
+        assert_eq!(report.reservations[3].offset, 400);
+        assert_eq!(report.reservations[3].increment, 1125);
+        assert!(report.trailing_data);
+        let message = crate::messages::AisMessage::DataLinkManagementMessage(report);
+        assert_eq!(message.out_of_spec_field(), Some("trailing_data"));
+    }
 }
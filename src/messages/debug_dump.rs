@@ -0,0 +1,215 @@
+//! Annotated bit dumps of messages, to help with writing and debugging parsers
+use super::parsers::sixbit_to_ascii;
+use super::unarmor;
+use crate::errors::Result;
+use crate::lib;
+use lib::std::fmt::Write;
+use lib::std::string::String;
+
+#[derive(Clone, Copy)]
+enum Kind {
+    Unsigned,
+    Signed,
+    Text,
+}
+
+use Kind::*;
+
+/// Field name, width in bits, and how to display the value
+type Field = (&'static str, usize, Kind);
+
+const HEADER: &[Field] = &[
+    ("message_type", 6, Unsigned),
+    ("repeat_indicator", 2, Unsigned),
+    ("mmsi", 30, Unsigned),
+];
+
+const POSITION_REPORT: &[Field] = &[
+    ("navigation_status", 4, Unsigned),
+    ("rate_of_turn", 8, Signed),
+    ("speed_over_ground", 10, Unsigned),
+    ("position_accuracy", 1, Unsigned),
+    ("longitude", 28, Signed),
+    ("latitude", 27, Signed),
+    ("course_over_ground", 12, Unsigned),
+    ("true_heading", 9, Unsigned),
+    ("timestamp", 6, Unsigned),
+    ("maneuver_indicator", 2, Unsigned),
+    ("spare", 3, Unsigned),
+    ("raim", 1, Unsigned),
+    ("radio_status", 19, Unsigned),
+];
+
+const STATIC_AND_VOYAGE_RELATED_DATA: &[Field] = &[
+    ("ais_version", 2, Unsigned),
+    ("imo_number", 30, Unsigned),
+    ("callsign", 42, Text),
+    ("vessel_name", 120, Text),
+    ("ship_type", 8, Unsigned),
+    ("dimension_to_bow", 9, Unsigned),
+    ("dimension_to_stern", 9, Unsigned),
+    ("dimension_to_port", 6, Unsigned),
+    ("dimension_to_starboard", 6, Unsigned),
+    ("epfd_type", 4, Unsigned),
+    ("eta_month_utc", 4, Unsigned),
+    ("eta_day_utc", 5, Unsigned),
+    ("eta_hour_utc", 5, Unsigned),
+    ("eta_minute_utc", 6, Unsigned),
+    ("draught", 8, Unsigned),
+    ("destination", 120, Text),
+    ("dte", 1, Unsigned),
+    ("spare", 1, Unsigned),
+];
+
+const STATIC_DATA_REPORT_PART_A: &[Field] = &[
+    ("part_number", 2, Unsigned),
+    ("vessel_name", 120, Text),
+    ("spare", 8, Unsigned),
+];
+
+const STATIC_DATA_REPORT_PART_B: &[Field] = &[
+    ("part_number", 2, Unsigned),
+    ("ship_type", 8, Unsigned),
+    ("vendor_id", 18, Text),
+    ("unit_model_code", 4, Unsigned),
+    ("serial_number", 20, Unsigned),
+    ("callsign", 42, Text),
+    ("dimension_to_bow", 9, Unsigned),
+    ("dimension_to_stern", 9, Unsigned),
+    ("dimension_to_port", 6, Unsigned),
+    ("dimension_to_starboard", 6, Unsigned),
+    ("spare", 6, Unsigned),
+];
+
+/// Bit-level view of an unarmored message
+struct Bits<'a> {
+    data: &'a [u8],
+    len: usize,
+}
+
+impl Bits<'_> {
+    fn bit(&self, offset: usize) -> bool {
+        self.data[offset / 8] & (0x80 >> (offset % 8)) != 0
+    }
+
+    fn unsigned(&self, offset: usize, width: usize) -> u64 {
+        (offset..offset + width).fold(0, |value, bit| (value << 1) | self.bit(bit) as u64)
+    }
+
+    fn signed(&self, offset: usize, width: usize) -> i64 {
+        let value = self.unsigned(offset, width) as i64;
+        if width > 0 && self.bit(offset) {
+            value - (1 << width)
+        } else {
+            value
+        }
+    }
+}
+
+/// Decodes `armored` (see [`unarmor()`](fn.unarmor.html)), and lists its bits, one field
+/// per line, with the offset, field name, raw bits, and value of each field. Fields
+/// are named for message types 1-3, 5, and 24; the bits of other message types are
+/// listed after the common header in groups of 8. Bits past the end of the known
+/// fields are listed as `unparsed`.
+///
+/// This is meant for developing and debugging parsers, and the format may change.
+///
+/// # Example
+/// ```
+/// let dump = ais::messages::debug_dump(b"13u?etPv2;0n:dDPwUM1U1Cb069D", 0)?;
+/// assert!(dump.lines().any(|line| line.contains("mmsi") && line.ends_with("= 265547250")));
+/// # Ok::<(), ais::errors::Error>(())
+/// ```
+pub fn debug_dump(armored: &[u8], fill_bits: usize) -> Result<String> {
+    let unarmored = unarmor(armored, fill_bits)?;
+    let bits = Bits {
+        data: &unarmored,
+        len: (armored.len() * 6).saturating_sub(fill_bits),
+    };
+    let body = match bits.unsigned(0, 6.min(bits.len)) {
+        1..=3 => POSITION_REPORT,
+        5 => STATIC_AND_VOYAGE_RELATED_DATA,
+        24 if bits.len >= 40 => match bits.unsigned(38, 2) {
+            0 => STATIC_DATA_REPORT_PART_A,
+            _ => STATIC_DATA_REPORT_PART_B,
+        },
+        _ => &[],
+    };
+    let mut output = String::new();
+    let mut offset = 0;
+    for &(name, width, kind) in HEADER.iter().chain(body) {
+        if offset >= bits.len {
+            break;
+        }
+        let width = width.min(bits.len - offset);
+        write_field(&mut output, &bits, offset, name, width, kind);
+        offset += width;
+    }
+    let name = if body.is_empty() { "bits" } else { "unparsed" };
+    while offset < bits.len {
+        let width = 8.min(bits.len - offset);
+        write_field(&mut output, &bits, offset, name, width, Unsigned);
+        offset += width;
+    }
+    Ok(output)
+}
+
+fn write_field(
+    output: &mut String,
+    bits: &Bits,
+    offset: usize,
+    name: &str,
+    width: usize,
+    kind: Kind,
+) {
+    let _ = write!(output, "{:>3} {:<22} ", offset, name);
+    for bit in offset..offset + width {
+        output.push(if bits.bit(bit) { '1' } else { '0' });
+    }
+    let _ = match kind {
+        Unsigned => write!(output, " = {}", bits.unsigned(offset, width)),
+        Signed => write!(output, " = {}", bits.signed(offset, width)),
+        Text => {
+            output.push_str(" = \"");
+            for symbol in (offset..offset + width - width % 6).step_by(6) {
+                let symbol = bits.unsigned(symbol, 6) as u8;
+                output.push(sixbit_to_ascii(symbol).unwrap_or(b'?') as char);
+            }
+            output.push('"');
+            Ok(())
+        }
+    };
+    output.push('\n');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dump_position_report() {
+        let dump = debug_dump(b"13u?etPv2;0n:dDPwUM1U1Cb069D", 0).unwrap();
+        let mmsi = dump.lines().find(|line| line.contains("mmsi")).unwrap();
+        assert_eq!(
+            mmsi,
+            "  8 mmsi                   001111110100111110110111110010 = 265547250"
+        );
+        assert!(dump.contains("\n 38 navigation_status      0000 = 0\n"));
+        assert!(dump
+            .lines()
+            .any(|line| line.starts_with("149 radio_status ")));
+        assert!(!dump.contains("unparsed"));
+    }
+
+    #[test]
+    fn dump_static_data_report() {
+        let dump = debug_dump(b"H6:lEgQL4r1<QDr0P4pN3KSKP00", 0).unwrap();
+        assert!(dump.contains("= \"WAN SHUN HANG 6868@@\""));
+    }
+
+    #[test]
+    fn dump_unknown_type() {
+        let dump = debug_dump(b"G02OHAP8aLvg@@b1tF600000;00", 0).unwrap();
+        assert!(dump.contains("\n 38 bits                   00000010 = 2\n"));
+    }
+}
@@ -1,6 +1,6 @@
 //! Standard Class B Position Report (type 18)
 use super::parsers::*;
-use super::AisMessageType;
+use super::{AisMessageType, CommonNavigation};
 use crate::errors::Result;
 use crate::lib;
 use nom::bits::{bits, complete::take as take_bits};
@@ -79,6 +79,20 @@ impl<'a> AisMessageType<'a> for DgnssBroadcastBinaryMessage {
     }
 }
 
+impl CommonNavigation for DgnssBroadcastBinaryMessage {
+    fn mmsi(&self) -> u32 {
+        self.mmsi
+    }
+
+    fn longitude(&self) -> Option<f32> {
+        self.longitude
+    }
+
+    fn latitude(&self) -> Option<f32> {
+        self.latitude
+    }
+}
+
 fn parse_longitude_min_10(data: i32) -> Option<f32> {
     match data {
         108_600 => None,
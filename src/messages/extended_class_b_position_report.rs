@@ -4,7 +4,7 @@ use super::parsers::*;
 use super::types::AssignedMode;
 use super::types::Dte;
 use super::types::EpfdType;
-use super::AisMessageType;
+use super::{AisMessageType, CommonNavigation};
 use crate::errors::Result;
 use crate::messages::types::ShipType;
 use nom::bits::{bits, complete::take as take_bits};
@@ -35,6 +35,28 @@ pub struct ExtendedClassBPositionReport {
     pub assigned_mode: AssignedMode,
 }
 
+impl ExtendedClassBPositionReport {
+    /// Whether the transponder reports itself as fully operational: its data terminal
+    /// is ready (so it can display and send text messages), it is operating
+    /// autonomously rather than in an assigned mode, and RAIM is in use to check
+    /// its position fix.
+    pub fn fully_operational(&self) -> bool {
+        self.dte == Dte::Ready && self.assigned_mode == AssignedMode::Autonomous && self.raim
+    }
+
+    /// Position of the reported point relative to the centre of the vessel, as
+    /// `(forward, starboard)` in metres. See
+    /// [`position_reference_offset()`](../navigation/fn.position_reference_offset.html).
+    pub fn position_reference_offset(&self) -> Option<(f32, f32)> {
+        position_reference_offset(
+            self.dimension_to_bow,
+            self.dimension_to_stern,
+            self.dimension_to_port,
+            self.dimension_to_starboard,
+        )
+    }
+}
+
 impl<'a> AisMessageType<'a> for ExtendedClassBPositionReport {
     fn name(&self) -> &'static str {
         "Extended Class B Position Report"
@@ -46,6 +68,20 @@ impl<'a> AisMessageType<'a> for ExtendedClassBPositionReport {
     }
 }
 
+impl CommonNavigation for ExtendedClassBPositionReport {
+    fn mmsi(&self) -> u32 {
+        self.mmsi
+    }
+
+    fn longitude(&self) -> Option<f32> {
+        self.longitude
+    }
+
+    fn latitude(&self) -> Option<f32> {
+        self.latitude
+    }
+}
+
 fn parse_base(data: &[u8]) -> IResult<&[u8], ExtendedClassBPositionReport> {
     bits(move |data| -> IResult<_, _> {
         let (data, message_type) = take_bits(6u8)(data)?;
@@ -131,4 +167,28 @@ mod tests {
         assert_eq!(report.dte, Dte::NotReady);
         assert_eq!(report.assigned_mode, AssignedMode::Autonomous);
     }
+
+    #[test]
+    fn test_position_reference_offset() {
+        let bytestream = b"C6:ijoP00:9NNF4TEspILDN0Vc0jNc1WWV0000000000S2<6R20P";
+        let bitstream = crate::messages::unarmor(bytestream, 0).unwrap();
+        let report = ExtendedClassBPositionReport::parse(bitstream.as_ref()).unwrap();
+        // 35 m to the bow and 13 m to the stern, on the centreline
+        assert_eq!(report.position_reference_offset(), Some((-11.0, 0.0)));
+    }
+
+    #[test]
+    fn test_fully_operational() {
+        let bytestream = b"C6:ijoP00:9NNF4TEspILDN0Vc0jNc1WWV0000000000S2<6R20P";
+        let bitstream = crate::messages::unarmor(bytestream, 0).unwrap();
+        let mut report = ExtendedClassBPositionReport::parse(bitstream.as_ref()).unwrap();
+        // DTE not ready, and no RAIM
+        assert!(!report.fully_operational());
+        report.dte = Dte::Ready;
+        assert!(!report.fully_operational());
+        report.raim = true;
+        assert!(report.fully_operational());
+        report.assigned_mode = AssignedMode::Assigned;
+        assert!(!report.fully_operational());
+    }
 }
@@ -1,4 +1,4 @@
-//! Base Station Report (type 4)
+//! Group Assignment Command (type 23)
 use crate::lib;
 use lib::std::time::Duration;
 
@@ -87,6 +87,17 @@ impl From<u8> for StationInterval {
     }
 }
 
+impl StationInterval {
+    /// The reporting interval as a fixed duration, if one was given. Intervals relative
+    /// to the station's current rate, and reserved values, return `None`.
+    pub fn as_duration(&self) -> Option<Duration> {
+        match self {
+            Self::Span(duration) => Some(*duration),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, PartialEq)]
 pub struct GroupAssignmentCommand {
     pub message_type: u8,
@@ -114,16 +125,24 @@ impl<'a> AisMessageType<'a> for GroupAssignmentCommand {
     }
 }
 
+/// Quiet time in minutes, with 0 meaning none
+fn parse_quiet_time(data: (&[u8], usize)) -> IResult<(&[u8], usize), Option<u8>> {
+    map(take_bits(4u8), |data| match data {
+        0 => None,
+        _ => Some(data),
+    })(data)
+}
+
 fn parse_base(data: &[u8]) -> IResult<&[u8], GroupAssignmentCommand> {
     bits(move |data| -> IResult<_, _> {
         let (data, message_type) = take_bits(6u8)(data)?;
         let (data, repeat_indicator) = take_bits(2u8)(data)?;
         let (data, mmsi) = take_bits(30u32)(data)?;
         let (data, _spare) = take_bits::<_, u8, _, _>(2u8)(data)?;
-        let (data, ne_longitude) = map(|data| signed_i32(data, 18), parse_longitude)(data)?;
-        let (data, ne_latitude) = map(|data| signed_i32(data, 17), parse_latitude)(data)?;
-        let (data, sw_longitude) = map(|data| signed_i32(data, 18), parse_longitude)(data)?;
-        let (data, sw_latitude) = map(|data| signed_i32(data, 17), parse_latitude)(data)?;
+        let (data, ne_longitude) = map(|data| signed_i32(data, 18), parse_longitude_min_10)(data)?;
+        let (data, ne_latitude) = map(|data| signed_i32(data, 17), parse_latitude_min_10)(data)?;
+        let (data, sw_longitude) = map(|data| signed_i32(data, 18), parse_longitude_min_10)(data)?;
+        let (data, sw_latitude) = map(|data| signed_i32(data, 17), parse_latitude_min_10)(data)?;
         let (data, station_type) = map(take_bits::<_, u8, _, _>(4u8), Into::into)(data)?;
         let (data, ship_type) = map(take_bits::<_, u8, _, _>(8u8), ShipType::parse)(data)?;
         let (data, _spare) = take_bits::<_, u32, _, _>(22u32)(data)?;
@@ -166,17 +185,27 @@ mod tests {
         assert_eq!(message.message_type, 23);
         assert_eq!(message.repeat_indicator, 0);
         assert_eq!(message.mmsi, 2611270);
-        f32_equal_naive(message.ne_longitude.unwrap(), 0.014758334);
-        f32_equal_naive(message.ne_latitude.unwrap(), 0.05353667);
-        f32_equal_naive(message.sw_longitude.unwrap(), 0.014213333);
-        f32_equal_naive(message.sw_latitude.unwrap(), 0.053053334);
+        f32_equal_naive(message.ne_longitude.unwrap(), 14.758333);
+        f32_equal_naive(message.ne_latitude.unwrap(), 53.536667);
+        f32_equal_naive(message.sw_longitude.unwrap(), 14.213333);
+        f32_equal_naive(message.sw_latitude.unwrap(), 53.053333);
         assert_eq!(
             message.station_type,
             StationType::RegionalUseAndInlandWaterways,
         );
         assert_eq!(message.ship_type, None);
         assert_eq!(message.tx_rx_mode, TransmitMode::TxATxBRxARxB);
-        assert_eq!(message.report_interval, StationInterval::NextLongerInterval);
+        assert_eq!(message.report_interval, StationInterval::Reserved(11));
         assert_eq!(message.quiet_time, None);
     }
+
+    #[test]
+    fn test_interval_duration() {
+        assert_eq!(
+            StationInterval::from(1).as_duration(),
+            Some(Duration::from_secs(600))
+        );
+        assert_eq!(StationInterval::NextLongerInterval.as_duration(), None);
+        assert_eq!(StationInterval::AsAutonomousMode.as_duration(), None);
+    }
 }
@@ -63,7 +63,6 @@ impl Station {
         } else {
             data
         };
-        // TODO: this is only dealing with 2 messages out of 3?
         Ok((data, Self { mmsi, messages }))
     }
 }
@@ -101,8 +100,12 @@ fn parse_message(data: &[u8]) -> IResult<&[u8], Interrogation> {
         let mut stations: StationList = Default::default();
         let (data, station) = Station::parse(data)?;
         push_unwrap(&mut stations, station);
+        // Up to three messages can be requested: two from the first station, and
+        // one from the second. Each message is followed by 2 spare bits, which
+        // must be skipped before the second station's MMSI.
         let remaining = remaining_bits(data);
-        let data = if remaining >= 30 {
+        let data = if remaining >= 32 {
+            let (data, _spare) = take_bits::<_, u8, _, _>(2u8)(data)?;
             let (data, station) = Station::parse(data)?;
             push_unwrap(&mut stations, station);
             take_bits::<_, u8, _, _>(2u8)(data)?.0
@@ -126,6 +129,7 @@ fn parse_message(data: &[u8]) -> IResult<&[u8], Interrogation> {
 mod tests {
     #![allow(clippy::unreadable_literal)]
     use super::*;
+    use crate::messages::bit_writer::BitWriter;
 
     #[test]
     fn test_type15_short() {
@@ -180,4 +184,53 @@ mod tests {
         assert_eq!(submessage.message_type, 3);
         assert!(submessage.slot_offset.is_none());
     }
+
+    #[test]
+    fn test_type15_two_stations() {
+        let mut message = BitWriter::new();
+        message
+            .u32(15, 6)
+            .u32(0, 2)
+            .u32(3669981, 30)
+            .u32(0, 2)
+            .u32(230682000, 30)
+            .u32(5, 6)
+            .u32(0, 12)
+            .u32(0, 2)
+            .u32(24, 6)
+            .u32(1234, 12)
+            .u32(0, 2)
+            .u32(470000200, 30)
+            .u32(3, 6)
+            .u32(56, 12)
+            .u32(0, 2);
+        assert_eq!(message.bit_len(), 160);
+        let message = Interrogation::parse(message.as_bytes()).unwrap();
+        assert_eq!(message.mmsi, 3669981);
+        assert_eq!(message.stations.len(), 2);
+        let station = &message.stations[0];
+        assert_eq!(station.mmsi, 230682000);
+        assert_eq!(
+            station.messages[..],
+            [
+                Message {
+                    message_type: 5,
+                    slot_offset: None
+                },
+                Message {
+                    message_type: 24,
+                    slot_offset: Some(1234)
+                }
+            ]
+        );
+        let station = &message.stations[1];
+        assert_eq!(station.mmsi, 470000200);
+        assert_eq!(
+            station.messages[..],
+            [Message {
+                message_type: 3,
+                slot_offset: Some(56)
+            }]
+        );
+    }
 }
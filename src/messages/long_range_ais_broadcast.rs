@@ -2,12 +2,16 @@
 use super::navigation::*;
 use super::parsers::*;
 use super::position_report::NavigationStatus;
-use super::AisMessageType;
+use super::{AisMessageType, CommonNavigation};
 use crate::errors::Result;
 use nom::bits::{bits, complete::take as take_bits};
 use nom::combinator::map;
 use nom::IResult;
 
+/// A position report sent for reception by satellite. The message itself doesn't
+/// say how it was received; where the feed names its receivers in tag blocks,
+/// [`TagBlock::is_satellite_source()`](crate::messages::tag_block::TagBlock::is_satellite_source)
+/// gives a hint.
 #[derive(Debug, PartialEq)]
 pub struct LongRangeAisBroadcastMessage {
     pub message_type: u8,
@@ -34,6 +38,20 @@ impl<'a> AisMessageType<'a> for LongRangeAisBroadcastMessage {
     }
 }
 
+impl CommonNavigation for LongRangeAisBroadcastMessage {
+    fn mmsi(&self) -> u32 {
+        self.mmsi
+    }
+
+    fn longitude(&self) -> Option<f32> {
+        self.longitude
+    }
+
+    fn latitude(&self) -> Option<f32> {
+        self.latitude
+    }
+}
+
 fn parse_base(data: &[u8]) -> IResult<&[u8], LongRangeAisBroadcastMessage> {
     bits(move |data| -> IResult<_, _> {
         let (data, message_type) = take_bits(6u8)(data)?;
@@ -43,31 +61,8 @@ fn parse_base(data: &[u8]) -> IResult<&[u8], LongRangeAisBroadcastMessage> {
         let (data, raim) = map(take_bits(1u8), u8_to_bool)(data)?;
         let (data, navigation_status) = map(take_bits(4u8), NavigationStatus::parse)(data)?;
 
-        let (data, longitude) = map(
-            |data| signed_i32(data, 18),
-            |lon| {
-                parse_longitude(lon).map(|val| {
-                    if message_type == 27 {
-                        val * 1000.0
-                    } else {
-                        val
-                    }
-                })
-            },
-        )(data)?;
-
-        let (data, latitude) = map(
-            |data| signed_i32(data, 17),
-            |lat| {
-                parse_latitude(lat).map(|val| {
-                    if message_type == 27 {
-                        val * 1000.0
-                    } else {
-                        val
-                    }
-                })
-            },
-        )(data)?;
+        let (data, longitude) = map(|data| signed_i32(data, 18), parse_longitude_min_10)(data)?;
+        let (data, latitude) = map(|data| signed_i32(data, 17), parse_latitude_min_10)(data)?;
 
         let (data, speed_over_ground) = map(take_bits(6u16), parse_speed_over_ground_62)(data)?;
         let (data, course_over_ground) = map(take_bits(9u16), parse_cog_511)(data)?;
@@ -114,6 +109,7 @@ mod tests {
     use crate::test_helpers::f32_equal_naive;
 
     use super::*;
+    use crate::messages::bit_writer::BitWriter;
     // use crate::test_helpers::*;
     #[test]
     fn test_type27_example() {
@@ -148,4 +144,30 @@ mod tests {
         f32_equal_naive(report.longitude.unwrap(), -13.368334);
         f32_equal_naive(report.latitude.unwrap(), -50.121665);
     }
+
+    #[test]
+    fn test_type27_position_not_available() {
+        let mut writer = BitWriter::new();
+        writer
+            .u32(27, 6)
+            .u32(0, 2)
+            .u32(1234567, 30)
+            .bool(false)
+            .bool(false)
+            .u32(15, 4)
+            .i32(108_600, 18)
+            .i32(54_600, 17)
+            .u32(63, 6)
+            .u32(511, 9)
+            .bool(false)
+            .bool(false);
+        let report = LongRangeAisBroadcastMessage::parse(writer.as_bytes()).unwrap();
+
+        assert_eq!(report.mmsi, 1234567);
+        assert_eq!(report.navigation_status, None);
+        assert_eq!(report.longitude, None);
+        assert_eq!(report.latitude, None);
+        assert_eq!(report.speed_over_ground, None);
+        assert_eq!(report.course_over_ground, None);
+    }
 }
@@ -7,13 +7,23 @@ pub mod addressed_safety_related;
 pub mod aid_to_navigation_report;
 pub mod assignment_mode_command;
 pub mod base_station_report;
+pub mod binary;
+pub mod binary_acknowledge;
 pub mod binary_addressed;
 pub mod binary_broadcast_message;
+pub mod bit_writer;
+pub mod channel_management;
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub mod custom;
 pub mod data_link_management_message;
+#[cfg(any(feature = "std", feature = "alloc"))]
+mod debug_dump;
 pub mod dgnss_broadcast_binary_message;
 pub mod extended_class_b_position_report;
+pub mod group_assignment_command;
 pub mod interrogation;
 pub mod long_range_ais_broadcast;
+pub mod multiple_slot_binary;
 pub mod navigation;
 #[cfg(all(not(feature = "std"), not(feature = "alloc")))]
 mod nom_noalloc;
@@ -22,20 +32,34 @@ pub mod position_report;
 pub mod radio_status;
 pub mod safety_related_acknowledgment;
 pub mod safety_related_broadcast;
+pub mod single_slot_binary;
 pub mod standard_aircraft_position_report;
 pub mod standard_class_b_position_report;
 pub mod static_and_voyage_related_data;
 pub mod static_data_report;
+pub mod tag_block;
 pub mod types;
 pub mod utc_date_inquiry;
 pub mod utc_date_response;
-pub mod binary_acknowledge;
 
-pub use parsers::message_type;
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub use debug_dump::debug_dump;
+pub use parsers::{message_type, normalize_name};
 
 #[cfg(feature = "alloc")]
 use crate::lib::std::{format, vec, vec::Vec};
 
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub type MmsiList = Vec<u32>;
+#[cfg(all(not(feature = "std"), not(feature = "alloc")))]
+pub type MmsiList = lib::std::vec::Vec<u32, 5>;
+
+/// Identifies a vessel across the messages it sends, for joining static and position
+/// reports. This is the sender's MMSI, except for auxiliary craft that report their
+/// parent ship, which share the parent's key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct VesselKey(pub u32);
+
 /// Contains all structured messages recognized by this crate
 #[derive(Debug, PartialEq)]
 pub enum AisMessage {
@@ -60,6 +84,382 @@ pub enum AisMessage {
     SafetyRelatedAcknowledgment(safety_related_acknowledgment::SafetyRelatedAcknowledge),
     LongRangeAisBroadcastMessage(long_range_ais_broadcast::LongRangeAisBroadcastMessage),
     BinaryAddressedMessage(binary_addressed::BinaryAddressedMessage),
+    GroupAssignmentCommand(group_assignment_command::GroupAssignmentCommand),
+    ChannelManagement(channel_management::ChannelManagement),
+    SingleSlotBinaryMessage(single_slot_binary::SingleSlotBinaryMessage),
+    MultipleSlotBinaryMessage(multiple_slot_binary::MultipleSlotBinaryMessage),
+    /// Decoded by a handler registered with
+    /// [`AisParser::register_handler()`](../sentence/struct.AisParser.html#method.register_handler)
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    Custom(custom::CustomMessage),
+}
+
+impl AisMessage {
+    /// Numeric message type, such as 1 for a Class A position report
+    pub fn message_type(&self) -> u8 {
+        match self {
+            Self::PositionReport(report) => report.message_type,
+            Self::BaseStationReport(report) => report.message_type,
+            Self::BinaryBroadcastMessage(report) => report.message_type,
+            Self::Interrogation(report) => report.message_type,
+            Self::StaticAndVoyageRelatedData(report) => report.message_type,
+            Self::DgnssBroadcastBinaryMessage(report) => report.message_type,
+            Self::StandardClassBPositionReport(report) => report.message_type,
+            Self::ExtendedClassBPositionReport(report) => report.message_type,
+            Self::DataLinkManagementMessage(report) => report.message_type,
+            Self::AidToNavigationReport(report) => report.message_type,
+            Self::StaticDataReport(report) => report.message_type,
+            Self::UtcDateResponse(report) => report.message_type,
+            Self::StandardAircraftPositionReport(report) => report.message_type,
+            Self::AssignmentModeCommand(report) => report.message_type,
+            Self::BinaryAcknowledgeMessage(report) => report.message_type,
+            Self::UtcDateInquiry(report) => report.message_type,
+            Self::AddressedSafetyRelatedMessage(report) => report.message_type,
+            Self::SafetyRelatedBroadcastMessage(report) => report.message_type,
+            Self::SafetyRelatedAcknowledgment(report) => report.message_type,
+            Self::LongRangeAisBroadcastMessage(report) => report.message_type,
+            Self::BinaryAddressedMessage(report) => report.message_type,
+            Self::GroupAssignmentCommand(report) => report.message_type,
+            Self::ChannelManagement(report) => report.message_type,
+            Self::SingleSlotBinaryMessage(report) => report.message_type,
+            Self::MultipleSlotBinaryMessage(report) => report.message_type,
+            #[cfg(any(feature = "std", feature = "alloc"))]
+            Self::Custom(report) => report.message_type(),
+        }
+    }
+
+    /// The common name for the message type
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::PositionReport(report) => report.name(),
+            Self::BaseStationReport(report) => report.name(),
+            Self::BinaryBroadcastMessage(report) => report.name(),
+            Self::Interrogation(report) => report.name(),
+            Self::StaticAndVoyageRelatedData(report) => report.name(),
+            Self::DgnssBroadcastBinaryMessage(report) => report.name(),
+            Self::StandardClassBPositionReport(report) => report.name(),
+            Self::ExtendedClassBPositionReport(report) => report.name(),
+            Self::DataLinkManagementMessage(report) => report.name(),
+            Self::AidToNavigationReport(report) => report.name(),
+            Self::StaticDataReport(report) => report.name(),
+            Self::UtcDateResponse(report) => report.name(),
+            Self::StandardAircraftPositionReport(report) => report.name(),
+            Self::AssignmentModeCommand(report) => report.name(),
+            Self::BinaryAcknowledgeMessage(report) => report.name(),
+            Self::UtcDateInquiry(report) => report.name(),
+            Self::AddressedSafetyRelatedMessage(report) => report.name(),
+            Self::SafetyRelatedBroadcastMessage(report) => report.name(),
+            Self::SafetyRelatedAcknowledgment(report) => report.name(),
+            Self::LongRangeAisBroadcastMessage(report) => report.name(),
+            Self::BinaryAddressedMessage(report) => report.name(),
+            Self::GroupAssignmentCommand(report) => report.name(),
+            Self::ChannelManagement(report) => report.name(),
+            Self::SingleSlotBinaryMessage(report) => report.name(),
+            Self::MultipleSlotBinaryMessage(report) => report.name(),
+            #[cfg(any(feature = "std", feature = "alloc"))]
+            Self::Custom(_) => "Custom Message",
+        }
+    }
+
+    /// MMSI of the station that sent this message
+    pub fn mmsi(&self) -> u32 {
+        match self {
+            Self::PositionReport(report) => report.mmsi,
+            Self::BaseStationReport(report) => report.mmsi,
+            Self::BinaryBroadcastMessage(report) => report.mmsi,
+            Self::Interrogation(report) => report.mmsi,
+            Self::StaticAndVoyageRelatedData(report) => report.mmsi,
+            Self::DgnssBroadcastBinaryMessage(report) => report.mmsi,
+            Self::StandardClassBPositionReport(report) => report.mmsi,
+            Self::ExtendedClassBPositionReport(report) => report.mmsi,
+            Self::DataLinkManagementMessage(report) => report.mmsi,
+            Self::AidToNavigationReport(report) => report.mmsi,
+            Self::StaticDataReport(report) => report.mmsi,
+            Self::UtcDateResponse(report) => report.mmsi,
+            Self::StandardAircraftPositionReport(report) => report.mmsi,
+            Self::AssignmentModeCommand(report) => report.mmsi,
+            Self::BinaryAcknowledgeMessage(report) => report.mmsi,
+            Self::UtcDateInquiry(report) => report.mmsi,
+            Self::AddressedSafetyRelatedMessage(report) => report.mmsi,
+            Self::SafetyRelatedBroadcastMessage(report) => report.mmsi,
+            Self::SafetyRelatedAcknowledgment(report) => report.mmsi,
+            Self::LongRangeAisBroadcastMessage(report) => report.mmsi,
+            Self::BinaryAddressedMessage(report) => report.mmsi,
+            Self::GroupAssignmentCommand(report) => report.mmsi,
+            Self::ChannelManagement(report) => report.mmsi,
+            Self::SingleSlotBinaryMessage(report) => report.mmsi,
+            Self::MultipleSlotBinaryMessage(report) => report.mmsi,
+            #[cfg(any(feature = "std", feature = "alloc"))]
+            Self::Custom(report) => report.mmsi(),
+        }
+    }
+
+    /// Key identifying the vessel that sent this message; see
+    /// [`VesselKey`](struct.VesselKey.html)
+    pub fn vessel_key(&self) -> VesselKey {
+        match self {
+            Self::StaticDataReport(report) => {
+                VesselKey(report.mothership_mmsi().unwrap_or(report.mmsi))
+            }
+            _ => VesselKey(self.mmsi()),
+        }
+    }
+
+    /// Reported position as `(latitude, longitude)`, in decimal degrees. Returns
+    /// `None` for messages that don't carry a position, or if it is not available.
+    pub fn position(&self) -> Option<(f32, f32)> {
+        let report = self.navigation()?;
+        Some((report.latitude()?, report.longitude()?))
+    }
+
+    /// The message's inner report, if it is one that reports a station's position
+    pub fn navigation(&self) -> Option<&dyn CommonNavigation> {
+        match self {
+            Self::PositionReport(report) => Some(report),
+            Self::BaseStationReport(report) => Some(report),
+            Self::DgnssBroadcastBinaryMessage(report) => Some(report),
+            Self::StandardClassBPositionReport(report) => Some(report),
+            Self::ExtendedClassBPositionReport(report) => Some(report),
+            Self::AidToNavigationReport(report) => Some(report),
+            Self::UtcDateResponse(report) => Some(report),
+            Self::StandardAircraftPositionReport(report) => Some(report),
+            Self::LongRangeAisBroadcastMessage(report) => Some(report),
+            _ => None,
+        }
+    }
+
+    /// The message's inner report, for downcasting to its concrete type, such as
+    /// [`PositionReport`](position_report/struct.PositionReport.html)
+    pub fn as_any(&self) -> &dyn lib::std::any::Any {
+        match self {
+            Self::PositionReport(report) => report,
+            Self::BaseStationReport(report) => report,
+            Self::BinaryBroadcastMessage(report) => report,
+            Self::Interrogation(report) => report,
+            Self::StaticAndVoyageRelatedData(report) => report,
+            Self::DgnssBroadcastBinaryMessage(report) => report,
+            Self::StandardClassBPositionReport(report) => report,
+            Self::ExtendedClassBPositionReport(report) => report,
+            Self::DataLinkManagementMessage(report) => report,
+            Self::AidToNavigationReport(report) => report,
+            Self::StaticDataReport(report) => report,
+            Self::UtcDateResponse(report) => report,
+            Self::StandardAircraftPositionReport(report) => report,
+            Self::AssignmentModeCommand(report) => report,
+            Self::BinaryAcknowledgeMessage(report) => report,
+            Self::UtcDateInquiry(report) => report,
+            Self::AddressedSafetyRelatedMessage(report) => report,
+            Self::SafetyRelatedBroadcastMessage(report) => report,
+            Self::SafetyRelatedAcknowledgment(report) => report,
+            Self::LongRangeAisBroadcastMessage(report) => report,
+            Self::BinaryAddressedMessage(report) => report,
+            Self::GroupAssignmentCommand(report) => report,
+            Self::ChannelManagement(report) => report,
+            Self::SingleSlotBinaryMessage(report) => report,
+            Self::MultipleSlotBinaryMessage(report) => report,
+            #[cfg(any(feature = "std", feature = "alloc"))]
+            Self::Custom(report) => report.report(),
+        }
+    }
+
+    /// MMSIs of all stations this message refers to: the sender first, followed
+    /// by any destinations, acknowledged or interrogated stations, or assigned stations
+    pub fn referenced_mmsis(&self) -> MmsiList {
+        let mut mmsis = MmsiList::default();
+        push_unwrap(&mut mmsis, self.mmsi());
+        match self {
+            Self::BinaryAddressedMessage(report) => push_unwrap(&mut mmsis, report.dest_mmsi),
+            Self::AddressedSafetyRelatedMessage(report) => {
+                push_unwrap(&mut mmsis, report.dest_mmsi)
+            }
+            Self::UtcDateInquiry(report) => push_unwrap(&mut mmsis, report.dest_mmsi),
+            Self::SingleSlotBinaryMessage(report) => {
+                if let Some(dest_mmsi) = report.dest_mmsi {
+                    push_unwrap(&mut mmsis, dest_mmsi);
+                }
+            }
+            Self::MultipleSlotBinaryMessage(report) => {
+                if let Some(dest_mmsi) = report.dest_mmsi {
+                    push_unwrap(&mut mmsis, dest_mmsi);
+                }
+            }
+            Self::BinaryAcknowledgeMessage(report) => {
+                for ack in report.acks.iter() {
+                    push_unwrap(&mut mmsis, ack.mmsi);
+                }
+            }
+            Self::SafetyRelatedAcknowledgment(report) => {
+                for ack in report.acks.iter() {
+                    push_unwrap(&mut mmsis, ack.mmsi);
+                }
+            }
+            Self::Interrogation(report) => {
+                for station in report.stations.iter() {
+                    push_unwrap(&mut mmsis, station.mmsi);
+                }
+            }
+            Self::ChannelManagement(report) => {
+                for mmsi in [report.dest1_mmsi, report.dest2_mmsi].into_iter().flatten() {
+                    push_unwrap(&mut mmsis, mmsi);
+                }
+            }
+            Self::AssignmentModeCommand(report) => {
+                push_unwrap(&mut mmsis, report.mmsi1);
+                if let Some(mmsi2) = report.mmsi2 {
+                    push_unwrap(&mut mmsis, mmsi2);
+                }
+            }
+            _ => {}
+        }
+        mmsis
+    }
+
+    /// Checks whether the sender's MMSI is plausible for this kind of message.
+    ///
+    /// MMSI 0 and values longer than nine digits are never valid. Identities with
+    /// a leading zero belong to coast stations (`00MIDxxxx`) and groups of ships
+    /// (`0MIDxxxxx`), so they are rejected for messages only sent by ships.
+    pub fn mmsi_plausible(&self) -> bool {
+        let mmsi = self.mmsi();
+        if mmsi == 0 || mmsi > 999_999_999 {
+            return false;
+        }
+        match self {
+            Self::PositionReport(_)
+            | Self::StaticAndVoyageRelatedData(_)
+            | Self::StandardClassBPositionReport(_)
+            | Self::ExtendedClassBPositionReport(_)
+            | Self::StaticDataReport(_)
+            | Self::LongRangeAisBroadcastMessage(_) => mmsi >= 100_000_000,
+            _ => true,
+        }
+    }
+
+    /// Applies [`normalize_name()`](fn.normalize_name.html) to the vessel name,
+    /// callsign, and destination fields of this message, where present
+    pub fn normalize_names(&mut self) {
+        match self {
+            Self::StaticAndVoyageRelatedData(report) => {
+                report.vessel_name = normalize_name(&report.vessel_name);
+                report.callsign = normalize_name(&report.callsign);
+                report.destination = normalize_name(&report.destination);
+            }
+            Self::ExtendedClassBPositionReport(report) => {
+                report.name = normalize_name(&report.name);
+            }
+            Self::AidToNavigationReport(report) => {
+                report.name = normalize_name(&report.name);
+            }
+            Self::StaticDataReport(report) => match &mut report.message_part {
+                static_data_report::MessagePart::PartA { vessel_name } => {
+                    *vessel_name = normalize_name(vessel_name);
+                }
+                static_data_report::MessagePart::PartB { callsign, .. } => {
+                    *callsign = normalize_name(callsign);
+                }
+                _ => {}
+            },
+            _ => {}
+        }
+    }
+
+    /// Returns the name of the first field holding an unknown or reserved value, if
+    /// any. Such values are passed through by the parser, but are not defined by the spec.
+    pub fn out_of_spec_field(&self) -> Option<&'static str> {
+        use position_report::NavigationStatus;
+        use static_data_report::MessagePart;
+        use types::{EpfdType, ShipType};
+
+        fn navigation_status(status: &Option<NavigationStatus>) -> bool {
+            matches!(
+                status,
+                Some(
+                    NavigationStatus::Reserved01
+                        | NavigationStatus::Reserved02
+                        | NavigationStatus::Reserved03
+                        | NavigationStatus::Unknown(_)
+                )
+            )
+        }
+        fn ship_type(ship_type: &Option<ShipType>) -> bool {
+            matches!(
+                ship_type,
+                Some(
+                    ShipType::Reserved(_)
+                        | ShipType::WingInGroundReserved(_)
+                        | ShipType::HighSpeedCraftReserved(_)
+                        | ShipType::PassengerReserved(_)
+                        | ShipType::CargoReserved(_)
+                        | ShipType::TankerReserved(_)
+                        | ShipType::OtherReserved(_)
+                )
+            )
+        }
+        fn epfd_type(epfd_type: &Option<EpfdType>) -> bool {
+            matches!(epfd_type, Some(EpfdType::Unknown(_)))
+        }
+
+        match self {
+            Self::PositionReport(report) => {
+                if navigation_status(&report.navigation_status) {
+                    Some("navigation_status")
+                } else if matches!(
+                    report.maneuver_indicator,
+                    Some(navigation::ManeuverIndicator::Unknown(_))
+                ) {
+                    Some("maneuver_indicator")
+                } else {
+                    None
+                }
+            }
+            Self::BaseStationReport(report) if epfd_type(&report.epfd_type) => Some("epfd_type"),
+            Self::UtcDateResponse(report) if epfd_type(&report.epfd_type) => Some("epfd_type"),
+            Self::StaticAndVoyageRelatedData(report) => {
+                if ship_type(&report.ship_type) {
+                    Some("ship_type")
+                } else if epfd_type(&report.epfd_type) {
+                    Some("epfd_type")
+                } else {
+                    None
+                }
+            }
+            Self::ExtendedClassBPositionReport(report) => {
+                if ship_type(&report.type_of_ship_and_cargo) {
+                    Some("type_of_ship_and_cargo")
+                } else if epfd_type(&report.epfd_type) {
+                    Some("epfd_type")
+                } else {
+                    None
+                }
+            }
+            Self::AidToNavigationReport(report) => {
+                if matches!(
+                    report.aid_type,
+                    Some(aid_to_navigation_report::NavaidType::Unknown(_))
+                ) {
+                    Some("aid_type")
+                } else if epfd_type(&report.epfd_type) {
+                    Some("epfd_type")
+                } else {
+                    None
+                }
+            }
+            Self::StaticDataReport(report) => match &report.message_part {
+                MessagePart::Unknown(_) => Some("message_part"),
+                MessagePart::PartB { ship_type: st, .. } if ship_type(st) => Some("ship_type"),
+                _ => None,
+            },
+            Self::DataLinkManagementMessage(report) if report.trailing_data => {
+                Some("trailing_data")
+            }
+            Self::LongRangeAisBroadcastMessage(report)
+                if navigation_status(&report.navigation_status) =>
+            {
+                Some("navigation_status")
+            }
+            _ => None,
+        }
+    }
 }
 
 /// Trait that describes specific types of AIS messages
@@ -70,12 +470,64 @@ pub trait AisMessageType<'a>: Sized {
     fn parse(data: &'a [u8]) -> Result<Self>;
 }
 
+/// Fields shared by messages that report the position of a station
+pub trait CommonNavigation {
+    /// MMSI of the station that sent this message
+    fn mmsi(&self) -> u32;
+    /// Longitude in decimal degrees, if available
+    fn longitude(&self) -> Option<f32>;
+    /// Latitude in decimal degrees, if available
+    fn latitude(&self) -> Option<f32>;
+}
+
+/// Message types that [`parse()`](fn.parse.html) knows how to decode
+const SUPPORTED_MESSAGE_TYPES: &[u8] = &[
+    1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26,
+    27,
+];
+
+/// Returns the list of message types that this library can decode, in ascending order
+pub fn supported_message_types() -> &'static [u8] {
+    SUPPORTED_MESSAGE_TYPES
+}
+
+/// Minimum length in bits of each message type, below which it cannot be decoded.
+/// Types with variable-length or optional fields require only the fields that are
+/// always present. Type 5 is nominally 424 bits, but truncated messages are common
+/// enough that the destination and DTE flag may be missing.
+fn minimum_bits(message_type: u8) -> usize {
+    match message_type {
+        1..=4 | 9 | 11 | 18 | 22 => 168,
+        5 => 302,
+        6 | 15 => 88,
+        7 | 10 | 12 | 13 | 20 => 72,
+        8 => 56,
+        14 | 25 => 40,
+        16 | 27 => 96,
+        17 => 80,
+        19 => 312,
+        21 => 272,
+        23 | 24 => 160,
+        26 => 60,
+        _ => 38,
+    }
+}
+
 /// Given an unarmored bitstream (see [`unarmor()`](fn.unarmor.html) for details), this
 /// will return a message type object, if supported by this library
 /// and the message is valid.
 ///
 pub fn parse(unarmored: &[u8]) -> Result<AisMessage> {
     let (_, result) = message_type(unarmored)?;
+    let expected = minimum_bits(result);
+    let found = unarmored.len() * 8;
+    if SUPPORTED_MESSAGE_TYPES.contains(&result) && found < expected {
+        return Err(crate::errors::Error::MessageTooShort {
+            message_type: result,
+            expected,
+            found,
+        });
+    }
     match result {
         1..=3 => Ok(AisMessage::PositionReport(
             position_report::PositionReport::parse(unarmored)?,
@@ -134,9 +586,21 @@ pub fn parse(unarmored: &[u8]) -> Result<AisMessage> {
         21 => Ok(AisMessage::AidToNavigationReport(
             aid_to_navigation_report::AidToNavigationReport::parse(unarmored)?,
         )),
+        22 => Ok(AisMessage::ChannelManagement(
+            channel_management::ChannelManagement::parse(unarmored)?,
+        )),
+        23 => Ok(AisMessage::GroupAssignmentCommand(
+            group_assignment_command::GroupAssignmentCommand::parse(unarmored)?,
+        )),
         24 => Ok(AisMessage::StaticDataReport(
             static_data_report::StaticDataReport::parse(unarmored)?,
         )),
+        25 => Ok(AisMessage::SingleSlotBinaryMessage(
+            single_slot_binary::SingleSlotBinaryMessage::parse(unarmored)?,
+        )),
+        26 => Ok(AisMessage::MultipleSlotBinaryMessage(
+            multiple_slot_binary::MultipleSlotBinaryMessage::parse(unarmored)?,
+        )),
         27 => Ok(AisMessage::LongRangeAisBroadcastMessage(
             long_range_ais_broadcast::LongRangeAisBroadcastMessage::parse(unarmored)?,
         )),
@@ -163,7 +627,7 @@ pub fn parse(unarmored: &[u8]) -> Result<AisMessage> {
 /// See <https://gpsd.gitlab.io/gpsd/AIVDM.html> for more details.
 pub fn unarmor(data: &[u8], fill_bits: usize) -> Result<AisRawData> {
     let bit_count = data.len() * 6;
-    let byte_count = (bit_count / 8) + ((bit_count % 8 != 0) as usize);
+    let byte_count = bit_count.div_ceil(8);
     #[cfg(any(feature = "std", feature = "alloc"))]
     let mut output = vec![0; byte_count];
     #[cfg(all(not(feature = "std"), not(feature = "alloc")))]
@@ -176,14 +640,7 @@ pub fn unarmor(data: &[u8], fill_bits: usize) -> Result<AisRawData> {
     };
     let mut offset = 0;
     for byte in data {
-        let unarmored = match *byte {
-            48..=87 => byte - 48,
-            96..=119 => byte - 56,
-            #[cfg(any(feature = "std", feature = "alloc"))]
-            _ => return Err(format!("Value out of range: {}", byte).into()),
-            #[cfg(all(not(feature = "std"), not(feature = "alloc")))]
-            _ => return Err("Armored byte value out of range".into()),
-        } << 2;
+        let unarmored = sixbit_symbol(*byte)? << 2;
         let offset_byte = offset / 8;
         let offset_bit = offset % 8;
         output[offset_byte] |= unarmored >> offset_bit;
@@ -218,6 +675,87 @@ pub fn unarmor(data: &[u8], fill_bits: usize) -> Result<AisRawData> {
     Ok(output)
 }
 
+/// Maximum number of armored payload characters placed in a single sentence, by
+/// convention, so that the sentence fits within the 82 character NMEA limit
+pub const MAX_SENTENCE_PAYLOAD_CHARS: usize = 60;
+
+/// Returns the number of sentences needed to carry an armored payload of
+/// `payload_len_chars` characters, splitting every
+/// [`MAX_SENTENCE_PAYLOAD_CHARS`](constant.MAX_SENTENCE_PAYLOAD_CHARS.html) characters
+pub fn fragment_count(payload_len_chars: usize) -> u8 {
+    let count = payload_len_chars
+        .div_ceil(MAX_SENTENCE_PAYLOAD_CHARS)
+        .max(1);
+    u8::try_from(count).unwrap_or(u8::MAX)
+}
+
+/// Converts a packed bitstream into 8-bit ASCII (armored) data; the inverse of
+/// [`unarmor()`](fn.unarmor.html).
+///
+/// The first `bit_len` bits of `data` are split into 6-bit symbols, with the final
+/// symbol padded with zero bits as needed. Returns the armored data, along with the
+/// number of fill bits that were added.
+///
+/// Returns an error if `data` holds fewer than `bit_len` bits.
+pub fn armor(data: &[u8], bit_len: usize) -> Result<(AisRawData, u8)> {
+    if bit_len > data.len() * 8 {
+        return Err("Bit length exceeds input data".into());
+    }
+    let symbol_count = bit_len.div_ceil(6);
+    let mut output = AisRawData::default();
+    for index in 0..symbol_count {
+        let offset = index * 6;
+        let symbol = (offset..offset + 6).fold(0u8, |symbol, bit| {
+            let value = bit < bit_len && data[bit / 8] & (0x80 >> (bit % 8)) != 0;
+            (symbol << 1) | value as u8
+        });
+        let byte = match symbol {
+            0..=39 => symbol + 48,
+            _ => symbol + 56,
+        };
+        #[cfg(any(feature = "std", feature = "alloc"))]
+        output.push(byte);
+        #[cfg(all(not(feature = "std"), not(feature = "alloc")))]
+        output
+            .push(byte)
+            .map_err(|_| crate::errors::Error::from("Armor output vector too large"))?;
+    }
+    Ok((output, (symbol_count * 6 - bit_len) as u8))
+}
+
+/// Converts 8-bit ASCII (armored) into individual 6-bit symbols, with values 0-63,
+/// one per input byte. Unlike [`unarmor()`](fn.unarmor.html), the symbols are not
+/// packed together, which makes this useful for inspecting the armoring itself.
+///
+/// Returns an error if any of the individual bytes cannot be converted
+/// to a valid 6-bit symbol.
+pub fn to_sixbit_symbols(armored: &[u8]) -> Result<AisRawData> {
+    let mut output = AisRawData::default();
+    for byte in armored {
+        let symbol = sixbit_symbol(*byte)?;
+        #[cfg(any(feature = "std", feature = "alloc"))]
+        output.push(symbol);
+        #[cfg(all(not(feature = "std"), not(feature = "alloc")))]
+        output
+            .push(symbol)
+            .map_err(|_| crate::errors::Error::from("Symbol output vector too large"))?;
+    }
+    Ok(output)
+}
+
+/// Converts a single armored byte into its 6-bit value
+#[inline]
+pub(crate) fn sixbit_symbol(byte: u8) -> Result<u8> {
+    match byte {
+        48..=87 => Ok(byte - 48),
+        96..=119 => Ok(byte - 56),
+        #[cfg(any(feature = "std", feature = "alloc"))]
+        _ => Err(format!("Value out of range: {}", byte).into()),
+        #[cfg(all(not(feature = "std"), not(feature = "alloc")))]
+        _ => Err("Armored byte value out of range".into()),
+    }
+}
+
 #[cfg(any(feature = "std", feature = "alloc"))]
 #[inline]
 fn push_unwrap<T>(list: &mut Vec<T>, item: T) {
@@ -297,5 +835,208 @@ mod tests {
         let result = unarmor(input, 3).unwrap();
         assert_eq!([0b0010_0111, 0b1001_1000, 0b0000_0000], &result[..]);
     }
+    #[test]
+    fn normalize_names() {
+        assert_eq!(normalize_name("WAN@@SHUN"), "WAN SHUN");
+        assert_eq!(normalize_name(" Sea  Breeze @"), "SEA BREEZE");
+        assert_eq!(normalize_name("@@@"), "");
+
+        let bitstream = unarmor(b"H6:lEgQL4r1<QDr0P4pN3KSKP00", 0).unwrap();
+        let mut message = parse(&bitstream).unwrap();
+        if let AisMessage::StaticDataReport(ref mut report) = message {
+            report.message_part = static_data_report::MessagePart::PartA {
+                vessel_name: "WAN@@SHUN".into(),
+            };
+        }
+        message.normalize_names();
+        if let AisMessage::StaticDataReport(report) = message {
+            assert_eq!(
+                report.message_part,
+                static_data_report::MessagePart::PartA {
+                    vessel_name: "WAN SHUN".into()
+                }
+            );
+        } else {
+            panic!("Expected static data report");
+        }
+    }
+
+    #[test]
+    fn fragments_needed() {
+        assert_eq!(fragment_count(0), 1);
+        assert_eq!(fragment_count(28), 1);
+        assert_eq!(fragment_count(60), 1);
+        // Type 5 is 424 bits, or 71 characters
+        assert_eq!(fragment_count(71), 2);
+    }
+
+    #[test]
+    fn armor_with_fill() {
+        let (armored, fill_bits) = armor(&[0b1011_0111, 0b1100_0000], 10).unwrap();
+        assert_eq!(&armored[..], b"et");
+        assert_eq!(fill_bits, 2);
+        assert!(armor(&[0xff], 9).is_err());
+    }
+
+    #[test]
+    fn sixbit_symbols() {
+        let result = to_sixbit_symbols(b"09W`qw").unwrap();
+        assert_eq!([0, 9, 39, 40, 57, 63], &result[..]);
+        assert!(to_sixbit_symbols(b"9X").is_err());
+    }
+
+    #[test]
+    fn supported_types() {
+        let types = supported_message_types();
+        for message_type in [1, 5, 21, 27] {
+            assert!(types.contains(&message_type));
+        }
+        assert!(!types.contains(&28));
+    }
+
+    #[test]
+    fn supported_types_are_dispatched() {
+        let mut data = [0; 126];
+        for &message_type in supported_message_types() {
+            // Long enough to pass the length check, so decoding reaches the type's parser
+            let length = minimum_bits(message_type).div_ceil(8);
+            data[0] = message_type << 2;
+            match parse(&data[..length]) {
+                Ok(_) => {}
+                Err(crate::errors::Error::Nmea { msg }) => {
+                    assert!(!msg.contains("Unimplemented"), "type {}", message_type)
+                }
+                Err(err) => panic!("type {}: {:?}", message_type, err),
+            }
+        }
+    }
+
+    #[test]
+    fn unsupported_types_are_unimplemented() {
+        let mut data = [0; 126];
+        for message_type in 0..64 {
+            if supported_message_types().contains(&message_type) {
+                continue;
+            }
+            data[0] = message_type << 2;
+            match parse(&data) {
+                Err(crate::errors::Error::Nmea { msg }) => {
+                    assert!(msg.contains("Unimplemented"), "type {}", message_type)
+                }
+                other => panic!("type {}: {:?}", message_type, other),
+            }
+        }
+    }
+
+    #[test]
+    fn reject_short_static_data() {
+        let data = [5 << 2; 37];
+        assert_eq!(
+            parse(&data),
+            Err(crate::errors::Error::MessageTooShort {
+                message_type: 5,
+                expected: 302,
+                found: 296,
+            })
+        );
+    }
+
+    #[test]
+    fn downcast_as_any() {
+        let bitstream = unarmor(b"13u?etPv2;0n:dDPwUM1U1Cb069D", 0).unwrap();
+        let message = parse(&bitstream).unwrap();
+        let report = message
+            .as_any()
+            .downcast_ref::<position_report::PositionReport>()
+            .unwrap();
+        assert_eq!(report.mmsi, 265547250);
+        assert!(message
+            .as_any()
+            .downcast_ref::<base_station_report::BaseStationReport>()
+            .is_none());
+    }
+
+    #[test]
+    fn common_navigation() {
+        let bitstream = unarmor(b"B6:hQDh0029Pt<4TAS003h6TSP00", 0).unwrap();
+        let message = parse(&bitstream).unwrap();
+        let report = message.navigation().unwrap();
+        assert_eq!(report.mmsi(), message.mmsi());
+        let (latitude, longitude) = message.position().unwrap();
+        assert_eq!(Some(latitude), report.latitude());
+        assert_eq!(Some(longitude), report.longitude());
+        assert!((longitude - 120.16217).abs() < 1e-4);
+
+        let bitstream = unarmor(b"?03Owo@nwsI0D00", 2).unwrap();
+        let message = parse(&bitstream).unwrap();
+        assert!(message.navigation().is_none());
+        assert!(message.position().is_none());
+    }
+
+    #[test]
+    fn mmsi_plausibility() {
+        let bitstream = unarmor(b"13u?etPv2;0n:dDPwUM1U1Cb069D", 0).unwrap();
+        let mut message = parse(&bitstream).unwrap();
+        assert_eq!(message.mmsi(), 265547250);
+        assert!(message.mmsi_plausible());
+        if let AisMessage::PositionReport(ref mut report) = message {
+            report.mmsi = 0;
+        }
+        assert!(!message.mmsi_plausible());
+        if let AisMessage::PositionReport(ref mut report) = message {
+            report.mmsi = 3669145;
+        }
+        assert!(!message.mmsi_plausible());
+
+        // Coast station identity is fine for a base station
+        let bitstream = unarmor(b"403OtVAv7=i?;o?IaHE`4Iw020S:", 0).unwrap();
+        let message = parse(&bitstream).unwrap();
+        assert_eq!(message.mmsi(), 3669145);
+        assert!(message.mmsi_plausible());
+    }
+
+    #[test]
+    fn referenced_mmsis_interrogation() {
+        let bitstream = unarmor(b"?03Owo@nwsI0D00", 2).unwrap();
+        let message = parse(&bitstream).unwrap();
+        assert_eq!(&message.referenced_mmsis()[..], [3669981, 230682000]);
+    }
+
+    #[test]
+    fn vessel_key() {
+        let bitstream = unarmor(b"13u?etPv2;0n:dDPwUM1U1Cb069D", 0).unwrap();
+        let message = parse(&bitstream).unwrap();
+        assert_eq!(message.vessel_key(), VesselKey(265547250));
+
+        let mut tender = bit_writer::BitWriter::new();
+        tender
+            .u32(24, 6)
+            .u32(0, 2)
+            .u32(982655472, 30)
+            .u32(1, 2)
+            .u32(37, 8)
+            .string6("SRT", 18)
+            .u32(0, 24)
+            .string6("", 42)
+            .u32(265547250, 30)
+            .u32(0, 6);
+        let message = parse(tender.as_bytes()).unwrap();
+        assert_eq!(message.mmsi(), 982655472);
+        assert_eq!(message.vessel_key(), VesselKey(265547250));
+    }
+
+    #[test]
+    fn reject_short_message() {
+        let data = [1 << 2, 0, 0];
+        assert_eq!(
+            parse(&data),
+            Err(crate::errors::Error::MessageTooShort {
+                message_type: 1,
+                expected: 168,
+                found: 24,
+            })
+        );
+    }
+
     // TODO: test parse i32
 }
@@ -0,0 +1,172 @@
+//! Multiple Slot Binary Message (type 26)
+use super::binary::{parse_binary, BinaryPayload};
+use super::parsers::*;
+use super::radio_status::{parse_radio, RadioStatus};
+use super::single_slot_binary::parse_data;
+use super::{push_unwrap, AisMessageType};
+use crate::errors::Result;
+use crate::lib;
+use nom::bits::{bits, complete::take as take_bits};
+use nom::combinator::{cond, map};
+use nom::IResult;
+
+#[cfg(all(not(feature = "std"), not(feature = "alloc")))]
+const MAX_DATA_SIZE_BYTES: usize = 126;
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub type MessageData = lib::std::vec::Vec<u8>;
+#[cfg(all(not(feature = "std"), not(feature = "alloc")))]
+pub type MessageData = lib::std::vec::Vec<u8, MAX_DATA_SIZE_BYTES>;
+
+/// Binary message sent in up to five slots, using SOTDMA or ITDMA reservations. The
+/// layout matches [`SingleSlotBinaryMessage`](../single_slot_binary/struct.SingleSlotBinaryMessage.html),
+/// followed by a radio status.
+#[derive(Debug, PartialEq)]
+pub struct MultipleSlotBinaryMessage {
+    pub message_type: u8,
+    pub repeat_indicator: u8,
+    pub mmsi: u32,
+    pub addressed: bool,
+    pub structured: bool,
+    /// Only present if `addressed` is set
+    pub dest_mmsi: Option<u32>,
+    /// Only present if `structured` is set
+    pub dac: Option<u16>,
+    /// Only present if `structured` is set
+    pub fid: Option<u8>,
+    /// Binary data, packed into bytes from the first bit. If the data isn't a whole
+    /// number of bytes, the final byte is padded with zero bits.
+    pub data: MessageData,
+    pub radio_status: RadioStatus,
+}
+
+impl MultipleSlotBinaryMessage {
+    /// Decodes the application data carried by this message, based on its DAC and
+    /// FID. Unstructured data has no DAC or FID to go by, so gives `None`.
+    pub fn decode_payload(&self) -> Option<BinaryPayload> {
+        match (self.dac, self.fid) {
+            (Some(dac), Some(fid)) => Some(parse_binary(dac, fid, &self.data)),
+            _ => None,
+        }
+    }
+}
+
+impl<'a> AisMessageType<'a> for MultipleSlotBinaryMessage {
+    fn name(&self) -> &'static str {
+        "Multiple Slot Binary Message"
+    }
+
+    fn parse(data: &[u8]) -> Result<Self> {
+        let (_, report) = parse_base(data)?;
+        Ok(report)
+    }
+}
+
+fn parse_base(data: &[u8]) -> IResult<&[u8], MultipleSlotBinaryMessage> {
+    bits(move |data| -> IResult<_, _> {
+        let (data, message_type) = take_bits(6u8)(data)?;
+        let (data, repeat_indicator) = take_bits(2u8)(data)?;
+        let (data, mmsi) = take_bits(30u32)(data)?;
+        let (data, addressed) = map(take_bits(1u8), u8_to_bool)(data)?;
+        let (data, structured) = map(take_bits(1u8), u8_to_bool)(data)?;
+        let (data, dest_mmsi) = cond(addressed, take_bits(30u32))(data)?;
+        let (data, dac) = cond(structured, take_bits(10u16))(data)?;
+        let (data, fid) = cond(structured, take_bits(6u8))(data)?;
+        // The radio status takes up the final 20 bits. The unarmored bitstream is
+        // padded to a whole byte, so this assumes the message ends on a byte boundary.
+        let data_len = remaining_bits(data).saturating_sub(20);
+        let (data, payload) = parse_data(data, data_len, push_unwrap)?;
+        let (data, radio_status) = parse_radio(data, message_type)?;
+        Ok((
+            data,
+            MultipleSlotBinaryMessage {
+                message_type,
+                repeat_indicator,
+                mmsi,
+                addressed,
+                structured,
+                dest_mmsi,
+                dac,
+                fid,
+                data: payload,
+                radio_status,
+            },
+        ))
+    })(data)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unreadable_literal)]
+    use super::*;
+    use crate::messages::bit_writer::BitWriter;
+    use crate::messages::radio_status::{SotdmaMessage, SubMessage, SyncState};
+    use crate::messages::AisMessage;
+    use crate::sentence::{AisFragments, AisParser};
+
+    #[test]
+    fn test_addressed_unstructured() {
+        let mut message = BitWriter::new();
+        message
+            .u32(26, 6)
+            .u32(0, 2)
+            .u32(3669145, 30)
+            .bool(true)
+            .bool(false)
+            .u32(366999712, 30)
+            .u32(0x2abcd3, 22)
+            // ITDMA
+            .bool(true)
+            .u32(1, 2)
+            .u32(1234, 13)
+            .u32(2, 3)
+            .bool(true);
+        let report = MultipleSlotBinaryMessage::parse(message.as_bytes()).unwrap();
+        assert_eq!(report.mmsi, 3669145);
+        assert_eq!(report.decode_payload(), None);
+        assert!(report.addressed);
+        assert!(!report.structured);
+        assert_eq!(report.dest_mmsi, Some(366999712));
+        assert_eq!(report.dac, None);
+        assert_eq!(report.fid, None);
+        assert_eq!(&report.data[..], [0xaa, 0xf3, 0x4c]);
+        assert!(matches!(report.radio_status, RadioStatus::Itdma(_)));
+    }
+
+    #[test]
+    fn test_fragmented() {
+        let mut parser = AisParser::new();
+        let fragment_1 = b"!AIVDM,2,1,3,A,J3`soB40GA0A4Q<D5AHG61TJ6ihM7Q,0*41";
+        let fragment_2 = b"!AIVDM,2,2,3,A,tP8B8S92DV`<0b,0*37";
+        assert!(matches!(
+            parser.parse(fragment_1, true),
+            Ok((_, AisFragments::Incomplete(_)))
+        ));
+        let report = match parser.parse(fragment_2, true).unwrap().1 {
+            AisFragments::Complete(sentence) => match sentence.message {
+                Some(AisMessage::MultipleSlotBinaryMessage(report)) => report,
+                other => panic!("Expected multiple slot binary message, got {:?}", other),
+            },
+            other => panic!("Expected complete message, got {:?}", other),
+        };
+        assert_eq!(report.message_type, 26);
+        assert_eq!(report.mmsi, 244250440);
+        assert!(!report.addressed);
+        assert!(report.structured);
+        assert_eq!(report.dest_mmsi, None);
+        assert_eq!(report.dac, Some(1));
+        assert_eq!(report.fid, Some(29));
+        assert_eq!(report.data.len(), 24);
+        assert_eq!(report.data[0], 0x10);
+        assert_eq!(report.data[22], 0x26);
+        assert_eq!(report.data[23], 0b1010_0000);
+        assert_eq!(
+            report.radio_status,
+            RadioStatus::Sotdma(SotdmaMessage {
+                sync_state: SyncState::UtcDirect,
+                slot_timeout: 3,
+                sub_message: SubMessage::ReceivedStations(42),
+            })
+        );
+    }
+}
@@ -1,3 +1,11 @@
+use crate::lib;
+use lib::std::fmt::Write;
+
+#[cfg(all(not(feature = "std"), not(feature = "alloc")))]
+pub type CoordinateString = lib::std::string::String<16>;
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub type CoordinateString = lib::std::string::String;
+
 pub fn parse_speed_over_ground(data: u16) -> Option<f32> {
     match data {
         1023 => None,
@@ -19,6 +27,26 @@ pub fn parse_latitude(data: i32) -> Option<f32> {
     }
 }
 
+/// Parse a longitude in 1/10 minute resolution rather than the usual 1/10000
+/// minute, as used by Long Range AIS Broadcast (type 27) and Group Assignment
+/// Command (type 23) messages
+pub fn parse_longitude_min_10(data: i32) -> Option<f32> {
+    match data {
+        108_600 => None, // 181 degrees: longitude not available
+        _ => parse_longitude(data).map(|val| val * 1000.0),
+    }
+}
+
+/// Parse a latitude in 1/10 minute resolution rather than the usual 1/10000
+/// minute, as used by Long Range AIS Broadcast (type 27) and Group Assignment
+/// Command (type 23) messages
+pub fn parse_latitude_min_10(data: i32) -> Option<f32> {
+    match data {
+        54_600 => None, // 91 degrees: latitude not available
+        _ => parse_latitude(data).map(|val| val * 1000.0),
+    }
+}
+
 pub fn parse_cog(data: u16) -> Option<f32> {
     match data {
         3600 => None,
@@ -33,6 +61,72 @@ pub fn parse_heading(data: u16) -> Option<u16> {
     }
 }
 
+/// Computes the position of the reference point (usually the GPS antenna) relative
+/// to the centre of the vessel, in metres, from its distances to the bow, stern,
+/// port side, and starboard side. The offset is returned as `(forward, starboard)`;
+/// negative values are aft of, or to port of, the centre. Returns `None` if the
+/// dimensions are not available.
+pub fn position_reference_offset(
+    dimension_to_bow: u16,
+    dimension_to_stern: u16,
+    dimension_to_port: u16,
+    dimension_to_starboard: u16,
+) -> Option<(f32, f32)> {
+    if dimension_to_bow + dimension_to_stern == 0 || dimension_to_port + dimension_to_starboard == 0
+    {
+        return None;
+    }
+    let forward = (dimension_to_stern as f32 - dimension_to_bow as f32) / 2.0;
+    let starboard = (dimension_to_port as f32 - dimension_to_starboard as f32) / 2.0;
+    Some((forward, starboard))
+}
+
+/// Formats a coordinate in decimal degrees as degrees and decimal minutes, with a
+/// hemisphere suffix, as seen on marine displays: for example, `37°48.417'N`.
+/// `is_latitude` selects between `N`/`S` and `E`/`W`.
+pub fn to_dms(coord: f64, is_latitude: bool) -> CoordinateString {
+    let hemisphere = match (is_latitude, coord < 0.0) {
+        (true, false) => 'N',
+        (true, true) => 'S',
+        (false, false) => 'E',
+        (false, true) => 'W',
+    };
+    let magnitude = if coord < 0.0 { -coord } else { coord };
+    // Round to thousandths of a minute up front, so minutes never display as 60
+    let thousandths = (magnitude * 60_000.0 + 0.5) as u64;
+    let degrees = thousandths / 60_000;
+    let minutes = thousandths % 60_000;
+    let mut output = CoordinateString::new();
+    // Always fits, even without an allocator
+    let _ = write!(
+        output,
+        "{}°{:02}.{:03}'{}",
+        degrees,
+        minutes / 1000,
+        minutes % 1000,
+        hemisphere
+    );
+    output
+}
+
+/// A geographic area bounded by latitude and longitude, in decimal degrees. Areas
+/// crossing the antimeridian are not supported.
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct BoundingBox {
+    pub min_latitude: f32,
+    pub min_longitude: f32,
+    pub max_latitude: f32,
+    pub max_longitude: f32,
+}
+
+impl BoundingBox {
+    /// Checks whether the position is inside this area, including its edges
+    pub fn contains(&self, latitude: f32, longitude: f32) -> bool {
+        (self.min_latitude..=self.max_latitude).contains(&latitude)
+            && (self.min_longitude..=self.max_longitude).contains(&longitude)
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Copy, Clone)]
 pub enum Accuracy {
     Unaugmented,
@@ -104,3 +198,38 @@ impl ManeuverIndicator {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_dms() {
+        assert_eq!(to_dms(37.80695, true), "37°48.417'N");
+        assert_eq!(to_dms(-122.4, false), "122°24.000'W");
+        assert_eq!(to_dms(-33.0, true), "33°00.000'S");
+        assert_eq!(to_dms(0.0999999, false), "0°06.000'E");
+    }
+
+    #[test]
+    fn reference_offset() {
+        // Antenna 10 m from the bow of a 50 m vessel, 2 m to port of the centreline
+        assert_eq!(position_reference_offset(10, 40, 3, 7), Some((15.0, -2.0)));
+        assert_eq!(position_reference_offset(0, 0, 3, 7), None);
+        assert_eq!(position_reference_offset(10, 40, 0, 0), None);
+    }
+
+    #[test]
+    fn bounding_box() {
+        let area = BoundingBox {
+            min_latitude: 37.0,
+            min_longitude: -123.0,
+            max_latitude: 38.5,
+            max_longitude: -122.0,
+        };
+        assert!(area.contains(37.80695, -122.4));
+        assert!(area.contains(38.5, -123.0));
+        assert!(!area.contains(36.9, -122.4));
+        assert!(!area.contains(37.80695, 122.4));
+    }
+}
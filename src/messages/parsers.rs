@@ -102,6 +102,39 @@ pub fn parse_6bit_ascii(
     }
 }
 
+/// Cleans up a name decoded from 6-bit ASCII: letters are uppercased, stray `@`
+/// padding is treated as a space, and runs of spaces are collapsed into one.
+///
+/// Without an allocator, names longer than the `AsciiString` capacity are truncated.
+pub fn normalize_name(name: &str) -> AsciiString {
+    let mut normalized = AsciiString::new();
+    let words = name
+        .split(|c: char| c == '@' || c.is_whitespace())
+        .filter(|word| !word.is_empty());
+    for word in words {
+        if !normalized.is_empty() {
+            push_char(&mut normalized, ' ');
+        }
+        for c in word.chars() {
+            push_char(&mut normalized, c.to_ascii_uppercase());
+        }
+    }
+    normalized
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+#[inline]
+fn push_char(text: &mut AsciiString, c: char) {
+    text.push(c);
+}
+
+#[cfg(all(not(feature = "std"), not(feature = "alloc")))]
+#[inline]
+fn push_char(text: &mut AsciiString, c: char) {
+    // Silently truncates when full
+    let _ = text.push(c);
+}
+
 /// Gets the message type from the first byte of supplied data
 #[inline]
 pub fn message_type(data: &[u8]) -> IResult<&[u8], u8> {
@@ -115,7 +148,7 @@ pub fn message_type_bits(data: (&[u8], usize)) -> IResult<(&[u8], usize), u8> {
 }
 
 #[inline]
-fn sixbit_to_ascii(data: u8) -> Result<u8> {
+pub(crate) fn sixbit_to_ascii(data: u8) -> Result<u8> {
     #[cfg(any(feature = "std", feature = "alloc"))]
     match data {
         0..=31 => Ok(data + 64),
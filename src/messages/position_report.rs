@@ -2,7 +2,7 @@
 use super::navigation::*;
 use super::parsers::*;
 use super::radio_status::{parse_radio, RadioStatus};
-use super::AisMessageType;
+use super::{AisMessageType, CommonNavigation};
 use crate::errors::Result;
 use nom::bits::{bits, complete::take as take_bits};
 use nom::combinator::map;
@@ -27,6 +27,29 @@ pub struct PositionReport {
     pub radio_status: RadioStatus,
 }
 
+/// Rating of a reported position, for filtering by navigation quality
+#[derive(Debug, PartialEq, Eq, Copy, Clone, PartialOrd, Ord)]
+pub enum PositionQuality {
+    /// Neither differential correction nor RAIM
+    Low,
+    /// Either differential correction or RAIM, but not both
+    Medium,
+    /// Differentially corrected, and checked by RAIM
+    High,
+}
+
+impl PositionReport {
+    /// Rates the position, based on whether it is differentially corrected
+    /// (`position_accuracy`) and whether RAIM is in use
+    pub fn position_quality(&self) -> PositionQuality {
+        match (self.position_accuracy, self.raim) {
+            (Accuracy::Dgps, true) => PositionQuality::High,
+            (Accuracy::Dgps, false) | (Accuracy::Unaugmented, true) => PositionQuality::Medium,
+            (Accuracy::Unaugmented, false) => PositionQuality::Low,
+        }
+    }
+}
+
 impl<'a> AisMessageType<'a> for PositionReport {
     fn name(&self) -> &'static str {
         "Position Report Class A"
@@ -38,6 +61,20 @@ impl<'a> AisMessageType<'a> for PositionReport {
     }
 }
 
+impl CommonNavigation for PositionReport {
+    fn mmsi(&self) -> u32 {
+        self.mmsi
+    }
+
+    fn longitude(&self) -> Option<f32> {
+        self.longitude
+    }
+
+    fn latitude(&self) -> Option<f32> {
+        self.latitude
+    }
+}
+
 fn parse_base(data: &[u8]) -> IResult<&[u8], PositionReport> {
     bits(move |data| -> IResult<_, _> {
         let (data, message_type) = take_bits(6u8)(data)?;
@@ -219,4 +256,21 @@ mod tests {
             Some(ManeuverIndicator::Unknown(3))
         );
     }
+
+    #[test]
+    fn test_position_quality() {
+        let bitstream = crate::messages::unarmor(b"13u?etPv2;0n:dDPwUM1U1Cb069D", 0).unwrap();
+        let position = PositionReport::parse(bitstream.as_ref()).unwrap();
+        assert_eq!(position.position_quality(), PositionQuality::Low);
+
+        let bitstream = crate::messages::unarmor(b"33nQ:B50000FiEBRjpcK19qSR>`<", 0).unwrap();
+        let position = PositionReport::parse(bitstream.as_ref()).unwrap();
+        assert_eq!(position.position_quality(), PositionQuality::Medium);
+
+        let bitstream = crate::messages::unarmor(b"38Id705000rRVJhE7cl9n;160000", 0).unwrap();
+        let mut position = PositionReport::parse(bitstream.as_ref()).unwrap();
+        assert_eq!(position.position_quality(), PositionQuality::Medium);
+        position.raim = true;
+        assert_eq!(position.position_quality(), PositionQuality::High);
+    }
 }
@@ -101,7 +101,9 @@ impl SotdmaMessage {
 #[derive(Debug, PartialEq, Eq)]
 pub struct ItdmaMessage {
     pub sync_state: SyncState,
-    pub slot_increment: i16,
+    /// Offset to the next slot to be used, or zero if there are no more transmissions.
+    /// This is an unsigned 13-bit value.
+    pub slot_increment: u16,
     pub num_slots: u8,
     pub keep: bool,
 }
@@ -124,13 +126,140 @@ impl ItdmaMessage {
     }
 }
 
+/// Where the choice between SOTDMA and ITDMA communication state comes from
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum CommStateSelector {
+    /// Always SOTDMA, as in types 1, 2, 4 and 11
+    Sotdma,
+    /// Always ITDMA, as in type 3
+    Itdma,
+    /// A communication state selector flag precedes the communication state,
+    /// with 0 meaning SOTDMA and 1 ITDMA, as in types 9, 18 and 26
+    Flag,
+}
+
+impl CommStateSelector {
+    /// Returns how the communication state is selected for `msg_type`, if that
+    /// message type carries one
+    pub fn for_message_type(msg_type: u8) -> Option<Self> {
+        match msg_type {
+            1 | 2 | 4 | 11 => Some(Self::Sotdma),
+            3 => Some(Self::Itdma),
+            9 | 18 | 26 => Some(Self::Flag),
+            _ => None,
+        }
+    }
+}
+
+/// Parses a communication state, using `selector` to pick between SOTDMA and ITDMA.
+/// When the selector is a flag, it is read from the input first.
+pub fn parse_commstate(
+    input: (&[u8], usize),
+    selector: CommStateSelector,
+) -> IResult<(&[u8], usize), RadioStatus> {
+    match selector {
+        CommStateSelector::Sotdma => SotdmaMessage::parse(input),
+        CommStateSelector::Itdma => ItdmaMessage::parse(input),
+        CommStateSelector::Flag => {
+            let (input, flag) = take_bits::<_, u8, _, _>(1u8)(input)?;
+            match flag {
+                0 => SotdmaMessage::parse(input),
+                1 => ItdmaMessage::parse(input),
+                _ => unreachable!(),
+            }
+        }
+    }
+}
+
+/// Parses the communication state of a message of type `msg_type`, including the
+/// selector flag for types that have one
 pub fn parse_radio(input: (&[u8], usize), msg_type: u8) -> IResult<(&[u8], usize), RadioStatus> {
-    match msg_type {
-        1 | 2 | 4 | 11 | 9 => SotdmaMessage::parse(input),
-        3 => ItdmaMessage::parse(input),
-        _ => Err(nom::Err::Failure(nom::error::Error::new(
+    match CommStateSelector::for_message_type(msg_type) {
+        Some(selector) => parse_commstate(input, selector),
+        None => Err(nom::Err::Failure(nom::error::Error::new(
             input,
             ErrorKind::Digit,
         ))),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::messages::bit_writer::BitWriter;
+
+    #[test]
+    fn commstate_by_message_type() {
+        let mut writer = BitWriter::new();
+        writer.u32(0, 2).u32(0, 3).u32(2250, 14).u32(0, 5);
+        // Type 4 is always SOTDMA
+        let (_, radio_status) = parse_radio((writer.as_bytes(), 0), 4).unwrap();
+        assert_eq!(
+            radio_status,
+            RadioStatus::Sotdma(SotdmaMessage {
+                sync_state: SyncState::UtcDirect,
+                slot_timeout: 0,
+                sub_message: SubMessage::SlotOffset(2250),
+            })
+        );
+        // Type 3 is always ITDMA
+        let (_, radio_status) = parse_radio((writer.as_bytes(), 0), 3).unwrap();
+        assert!(matches!(radio_status, RadioStatus::Itdma(_)));
+        assert!(parse_radio((writer.as_bytes(), 0), 5).is_err());
+    }
+
+    #[test]
+    fn commstate_by_flag() {
+        let mut writer = BitWriter::new();
+        writer
+            .bool(true)
+            .u32(2, 2)
+            .u32(42, 13)
+            .u32(1, 3)
+            .bool(false);
+        let (_, radio_status) = parse_radio((writer.as_bytes(), 0), 18).unwrap();
+        assert_eq!(
+            radio_status,
+            RadioStatus::Itdma(ItdmaMessage {
+                sync_state: SyncState::BaseStation,
+                slot_increment: 42,
+                num_slots: 1,
+                keep: false,
+            })
+        );
+
+        let mut writer = BitWriter::new();
+        writer.bool(false).u32(0, 2).u32(3, 3).u32(17, 14);
+        let (_, radio_status) =
+            parse_commstate((writer.as_bytes(), 0), CommStateSelector::Flag).unwrap();
+        assert_eq!(
+            radio_status,
+            RadioStatus::Sotdma(SotdmaMessage {
+                sync_state: SyncState::UtcDirect,
+                slot_timeout: 3,
+                sub_message: SubMessage::ReceivedStations(17),
+            })
+        );
+    }
+
+    #[test]
+    fn itdma_large_slot_increment() {
+        let mut writer = BitWriter::new();
+        writer
+            .u32(1, 2)
+            .u32(8191, 13)
+            .u32(5, 3)
+            .bool(true)
+            .u32(0, 5);
+        let (_, radio_status) = ItdmaMessage::parse((writer.as_bytes(), 0)).unwrap();
+        assert_eq!(
+            radio_status,
+            RadioStatus::Itdma(ItdmaMessage {
+                sync_state: SyncState::UtcIndirect,
+                slot_increment: 8191,
+                num_slots: 5,
+                keep: true,
+            })
+        );
+    }
+}
@@ -14,6 +14,14 @@ pub struct SafetyRelatedBroadcastMessage {
     pub text: AsciiString,
 }
 
+impl SafetyRelatedBroadcastMessage {
+    /// Returns whether the message text contains `pattern`. The match is case-sensitive,
+    /// though 6-bit ASCII text is always uppercase.
+    pub fn text_contains(&self, pattern: &str) -> bool {
+        self.text.contains(pattern)
+    }
+}
+
 impl<'a> AisMessageType<'a> for SafetyRelatedBroadcastMessage {
     fn name(&self) -> &'static str {
         "Safety-Related Broadcast Message"
@@ -69,5 +77,7 @@ mod tests {
         assert_eq!(report.repeat_indicator, 0);
         assert_eq!(report.mmsi, 351809000);
         assert_eq!(report.text, "RCVD YR TEST MSG");
+        assert!(report.text_contains("TEST MSG"));
+        assert!(!report.text_contains("MAYDAY"));
     }
 }
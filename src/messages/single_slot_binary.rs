@@ -0,0 +1,206 @@
+//! Single Slot Binary Message (type 25)
+use super::binary::{parse_binary, BinaryPayload};
+use super::parsers::*;
+use super::{push_unwrap, AisMessageType};
+use crate::errors::Result;
+use crate::lib;
+use nom::bits::{bits, complete::take as take_bits};
+use nom::combinator::{cond, map};
+use nom::IResult;
+
+#[cfg(all(not(feature = "std"), not(feature = "alloc")))]
+const MAX_DATA_SIZE_BYTES: usize = 16;
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub type MessageData = lib::std::vec::Vec<u8>;
+#[cfg(all(not(feature = "std"), not(feature = "alloc")))]
+pub type MessageData = lib::std::vec::Vec<u8, MAX_DATA_SIZE_BYTES>;
+
+/// Short binary message, sent in a single slot without prior reservation. It may be
+/// broadcast or addressed, and its data may be structured (prefixed with a DAC and
+/// FID, as for types 6 and 8) or unstructured.
+#[derive(Debug, PartialEq)]
+pub struct SingleSlotBinaryMessage {
+    pub message_type: u8,
+    pub repeat_indicator: u8,
+    pub mmsi: u32,
+    pub addressed: bool,
+    pub structured: bool,
+    /// Only present if `addressed` is set
+    pub dest_mmsi: Option<u32>,
+    /// Only present if `structured` is set
+    pub dac: Option<u16>,
+    /// Only present if `structured` is set
+    pub fid: Option<u8>,
+    /// Binary data, packed into bytes from the first bit. If the data isn't a whole
+    /// number of bytes, the final byte is padded with zero bits.
+    pub data: MessageData,
+}
+
+impl SingleSlotBinaryMessage {
+    /// Decodes the application data carried by this message, based on its DAC and
+    /// FID. Unstructured data has no DAC or FID to go by, so gives `None`.
+    pub fn decode_payload(&self) -> Option<BinaryPayload> {
+        match (self.dac, self.fid) {
+            (Some(dac), Some(fid)) => Some(parse_binary(dac, fid, &self.data)),
+            _ => None,
+        }
+    }
+}
+
+impl<'a> AisMessageType<'a> for SingleSlotBinaryMessage {
+    fn name(&self) -> &'static str {
+        "Single Slot Binary Message"
+    }
+
+    fn parse(data: &[u8]) -> Result<Self> {
+        let (_, report) = parse_base(data)?;
+        Ok(report)
+    }
+}
+
+/// Copies `len` bits into a byte-aligned buffer, since the binary data in types 25
+/// and 26 doesn't necessarily start on a byte boundary
+pub(crate) fn parse_data<T: Default>(
+    data: (&[u8], usize),
+    len: usize,
+    push: fn(&mut T, u8),
+) -> IResult<(&[u8], usize), T> {
+    let mut output = T::default();
+    let mut data = data;
+    for _ in 0..len / 8 {
+        let (rest, byte) = take_bits(8u8)(data)?;
+        push(&mut output, byte);
+        data = rest;
+    }
+    let tail = len % 8;
+    if tail > 0 {
+        let (rest, byte) = take_bits::<_, u8, _, _>(tail)(data)?;
+        push(&mut output, byte << (8 - tail));
+        data = rest;
+    }
+    Ok((data, output))
+}
+
+fn parse_base(data: &[u8]) -> IResult<&[u8], SingleSlotBinaryMessage> {
+    bits(move |data| -> IResult<_, _> {
+        let (data, message_type) = take_bits(6u8)(data)?;
+        let (data, repeat_indicator) = take_bits(2u8)(data)?;
+        let (data, mmsi) = take_bits(30u32)(data)?;
+        let (data, addressed) = map(take_bits(1u8), u8_to_bool)(data)?;
+        let (data, structured) = map(take_bits(1u8), u8_to_bool)(data)?;
+        let (data, dest_mmsi) = cond(addressed, take_bits(30u32))(data)?;
+        let (data, dac) = cond(structured, take_bits(10u16))(data)?;
+        let (data, fid) = cond(structured, take_bits(6u8))(data)?;
+        let (data, payload) = parse_data(data, remaining_bits(data), push_unwrap)?;
+        Ok((
+            data,
+            SingleSlotBinaryMessage {
+                message_type,
+                repeat_indicator,
+                mmsi,
+                addressed,
+                structured,
+                dest_mmsi,
+                dac,
+                fid,
+                data: payload,
+            },
+        ))
+    })(data)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unreadable_literal)]
+    use super::*;
+    use crate::messages::bit_writer::BitWriter;
+
+    fn header(addressed: bool, structured: bool) -> BitWriter {
+        let mut message = BitWriter::new();
+        message
+            .u32(25, 6)
+            .u32(0, 2)
+            .u32(538003422, 30)
+            .bool(addressed)
+            .bool(structured);
+        message
+    }
+
+    #[test]
+    fn test_broadcast_unstructured() {
+        let mut message = header(false, false);
+        message.u32(0xdead, 16).u32(0b101, 3);
+        let report = SingleSlotBinaryMessage::parse(message.as_bytes()).unwrap();
+        assert_eq!(report.message_type, 25);
+        assert_eq!(report.mmsi, 538003422);
+        assert!(!report.addressed);
+        assert!(!report.structured);
+        assert_eq!(report.dest_mmsi, None);
+        assert_eq!(report.dac, None);
+        assert_eq!(report.fid, None);
+        assert_eq!(&report.data[..], [0xde, 0xad, 0b1010_0000]);
+    }
+
+    #[test]
+    fn test_broadcast_structured() {
+        let mut message = header(false, true);
+        message.u32(1, 10).u32(29, 6).u32(0x42, 8);
+        let report = SingleSlotBinaryMessage::parse(message.as_bytes()).unwrap();
+        assert!(!report.addressed);
+        assert!(report.structured);
+        assert_eq!(report.dest_mmsi, None);
+        assert_eq!(report.dac, Some(1));
+        assert_eq!(report.fid, Some(29));
+        assert_eq!(&report.data[..], [0x42]);
+    }
+
+    #[test]
+    fn test_addressed_unstructured() {
+        let mut message = header(true, false);
+        message.u32(366999712, 30).u32(0xbeef, 16);
+        let report = SingleSlotBinaryMessage::parse(message.as_bytes()).unwrap();
+        assert!(report.addressed);
+        assert!(!report.structured);
+        assert_eq!(report.dest_mmsi, Some(366999712));
+        assert_eq!(report.dac, None);
+        assert_eq!(report.fid, None);
+        // The 86-bit message is padded to 11 bytes, so 2 zero bits follow the data
+        assert_eq!(&report.data[..], [0xbe, 0xef, 0]);
+    }
+
+    #[test]
+    fn test_addressed_structured() {
+        let mut message = header(true, true);
+        message
+            .u32(366999712, 30)
+            .u32(366, 10)
+            .u32(56, 6)
+            .u32(0xcafe, 16);
+        let report = SingleSlotBinaryMessage::parse(message.as_bytes()).unwrap();
+        assert!(report.addressed);
+        assert!(report.structured);
+        assert_eq!(report.dest_mmsi, Some(366999712));
+        assert_eq!(report.dac, Some(366));
+        assert_eq!(report.fid, Some(56));
+        assert_eq!(&report.data[..], [0xca, 0xfe, 0]);
+    }
+
+    #[test]
+    fn test_decode_payload() {
+        let mut message = header(false, true);
+        message.u32(1, 10).u32(29, 6).u32(0, 10).string6("HI", 12);
+        let report = SingleSlotBinaryMessage::parse(message.as_bytes()).unwrap();
+        let description = match report.decode_payload() {
+            Some(BinaryPayload::TextDescription(description)) => description,
+            other => panic!("Expected text description, got {:?}", other),
+        };
+        assert_eq!(description.linkage_id, 0);
+        assert!(description.text.starts_with("HI"));
+
+        let mut message = header(false, false);
+        message.u32(0xdead, 16);
+        let report = SingleSlotBinaryMessage::parse(message.as_bytes()).unwrap();
+        assert_eq!(report.decode_payload(), None);
+    }
+}
@@ -5,7 +5,7 @@ use super::radio_status::*;
 
 use super::types::AssignedMode;
 use super::types::Dte;
-use super::AisMessageType;
+use super::{AisMessageType, CommonNavigation};
 use crate::errors::Result;
 use nom::bits::{bits, complete::take as take_bits};
 use nom::combinator::map;
@@ -40,6 +40,20 @@ impl<'a> AisMessageType<'a> for SARPositionReport {
     }
 }
 
+impl CommonNavigation for SARPositionReport {
+    fn mmsi(&self) -> u32 {
+        self.mmsi
+    }
+
+    fn longitude(&self) -> Option<f32> {
+        self.longitude
+    }
+
+    fn latitude(&self) -> Option<f32> {
+        self.latitude
+    }
+}
+
 fn parse_base(data: &[u8]) -> IResult<&[u8], SARPositionReport> {
     bits(move |data| -> IResult<_, _> {
         let (data, message_type) = take_bits(6u8)(data)?;
@@ -125,13 +139,9 @@ mod tests {
         assert_eq!(report.dte, Dte::NotReady);
         if let RadioStatus::Sotdma(radio_status) = report.radio_status {
             assert_eq!(radio_status.sync_state, SyncState::UtcDirect);
-            assert_eq!(radio_status.slot_timeout, 1);
-            if let SubMessage::UtcHourAndMinute(hour, minute) = radio_status.sub_message {
-                assert_eq!(hour, 0);
-                assert_eq!(minute, 14);
-            } else {
-                panic!("Expected UTC Hour and Minute submessage");
-            }
+            // The communication state follows a selector flag
+            assert_eq!(radio_status.slot_timeout, 2);
+            assert_eq!(radio_status.sub_message, SubMessage::SlotNumber(624));
         } else {
             panic!("Expected SOTDMA message");
         }
@@ -1,9 +1,9 @@
 //! Standard Class B Position Report (type 18)
 use super::navigation::*;
 use super::parsers::*;
-use super::radio_status::{ItdmaMessage, RadioStatus, SotdmaMessage};
+use super::radio_status::{parse_radio, RadioStatus};
 use super::types::AssignedMode;
-use super::AisMessageType;
+use super::{AisMessageType, CommonNavigation};
 use crate::errors::Result;
 use nom::bits::{bits, complete::take as take_bits};
 use nom::combinator::map;
@@ -42,6 +42,20 @@ impl<'a> AisMessageType<'a> for StandardClassBPositionReport {
     }
 }
 
+impl CommonNavigation for StandardClassBPositionReport {
+    fn mmsi(&self) -> u32 {
+        self.mmsi
+    }
+
+    fn longitude(&self) -> Option<f32> {
+        self.longitude
+    }
+
+    fn latitude(&self) -> Option<f32> {
+        self.latitude
+    }
+}
+
 #[derive(Debug, PartialEq, Eq)]
 pub enum CarrierSense {
     /// Class B SOTDMA unit
@@ -81,12 +95,7 @@ fn parse_base(data: &[u8]) -> IResult<&[u8], StandardClassBPositionReport> {
         let (data, accepts_message_22) = map(take_bits(1u8), u8_to_bool)(data)?;
         let (data, assigned_mode) = map(take_bits(1u8), AssignedMode::parse)(data)?;
         let (data, raim) = map(take_bits(1u8), u8_to_bool)(data)?;
-        let (data, cs_selector) = take_bits(1u8)(data)?;
-        let (data, radio_status) = match cs_selector {
-            0 => SotdmaMessage::parse(data)?,
-            1 => ItdmaMessage::parse(data)?,
-            _ => unreachable!(),
-        };
+        let (data, radio_status) = parse_radio(data, message_type)?;
         Ok((
             data,
             StandardClassBPositionReport {
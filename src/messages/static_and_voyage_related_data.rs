@@ -27,11 +27,25 @@ pub struct StaticAndVoyageRelatedData {
     pub eta_day_utc: Option<u8>,
     pub eta_hour_utc: u8,
     pub eta_minute_utc: Option<u8>,
+    /// Maximum present static draught, in meters, with 0.1 m resolution. 0 means
+    /// not available; see [`draught_meters()`](#method.draught_meters).
     pub draught: f32,
     pub destination: AsciiString,
     pub dte: Dte,
 }
 
+impl StaticAndVoyageRelatedData {
+    /// Draught in meters, or `None` if not available. Values of 25.5 m mean 25.5 m
+    /// or more.
+    pub fn draught_meters(&self) -> Option<f32> {
+        if self.draught == 0.0 {
+            None
+        } else {
+            Some(self.draught)
+        }
+    }
+}
+
 impl<'a> AisMessageType<'a> for StaticAndVoyageRelatedData {
     fn name(&self) -> &'static str {
         "Static and Voyage Related Data"
@@ -113,6 +127,7 @@ fn parse_message(data: &[u8]) -> IResult<&[u8], StaticAndVoyageRelatedData> {
 mod tests {
     #![allow(clippy::unreadable_literal)]
     use super::*;
+    use crate::messages::bit_writer::BitWriter;
     use crate::test_helpers::f32_equal_naive;
 
     #[test]
@@ -130,6 +145,7 @@ mod tests {
         );
         assert_eq!(message.eta_month_utc, Some(4));
         assert_eq!(message.destination, "ROTTERDAM");
+        assert_eq!(message.draught_meters(), None);
         assert_eq!(message.epfd_type, None);
         assert_eq!(message.dte, Dte::Ready);
     }
@@ -150,9 +166,55 @@ mod tests {
         assert_eq!(message.callsign, "PF8793");
         assert_eq!(message.ship_type, Some(ShipType::PleasureCraft));
         f32_equal_naive(message.draught, 2.1);
+        f32_equal_naive(message.draught_meters().unwrap(), 2.1);
         assert_eq!(message.eta_month_utc, Some(1));
         assert_eq!(message.destination, "NL LMMR");
         assert_eq!(message.epfd_type, None);
         assert_eq!(message.dte, Dte::Ready);
     }
+
+    #[test]
+    fn test_type5_invalid_ship_type() {
+        let mut message = BitWriter::new();
+        message
+            .u32(5, 6)
+            .u32(0, 2)
+            .u32(244250440, 30)
+            .u32(0, 2)
+            .u32(9134270, 30)
+            .string6("PF8793", 42)
+            .string6("NORDIC STAR", 120)
+            // Out of range, so decodes as `None`, but still takes up its 8 bits
+            .u32(100, 8)
+            .u32(120, 9)
+            .u32(30, 9)
+            .u32(10, 6)
+            .u32(12, 6)
+            .u32(1, 4)
+            .u32(5, 4)
+            .u32(17, 5)
+            .u32(8, 5)
+            .u32(30, 6)
+            .u32(64, 8)
+            .string6("NL LMMR", 120)
+            .bool(false)
+            .u32(0, 1);
+        let message = StaticAndVoyageRelatedData::parse(message.as_bytes()).unwrap();
+        assert_eq!(message.ship_type, None);
+        assert_eq!(message.imo_number, 9134270);
+        assert_eq!(message.callsign, "PF8793");
+        assert_eq!(message.vessel_name, "NORDIC STAR");
+        assert_eq!(message.dimension_to_bow, 120);
+        assert_eq!(message.dimension_to_stern, 30);
+        assert_eq!(message.dimension_to_port, 10);
+        assert_eq!(message.dimension_to_starboard, 12);
+        assert_eq!(message.epfd_type, Some(EpfdType::Gps));
+        assert_eq!(message.eta_month_utc, Some(5));
+        assert_eq!(message.eta_day_utc, Some(17));
+        assert_eq!(message.eta_hour_utc, 8);
+        assert_eq!(message.eta_minute_utc, Some(30));
+        f32_equal_naive(message.draught, 6.4);
+        assert_eq!(message.destination, "NL LMMR");
+        assert_eq!(message.dte, Dte::Ready);
+    }
 }
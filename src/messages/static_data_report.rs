@@ -27,6 +27,35 @@ impl<'a> AisMessageType<'a> for StaticDataReport {
     }
 }
 
+impl StaticDataReport {
+    /// Whether the sender is an auxiliary craft associated with a parent ship, such
+    /// as a tender, identified by an MMSI of the form `98MIDxxxx`
+    pub fn is_auxiliary_craft(&self) -> bool {
+        (980_000_000..990_000_000).contains(&self.mmsi)
+    }
+
+    /// MMSI of the parent ship, for Part B reports from an auxiliary craft. These
+    /// send the parent's MMSI in place of the vessel dimensions, so it is rebuilt
+    /// from the bits of the dimension fields.
+    pub fn mothership_mmsi(&self) -> Option<u32> {
+        match self.message_part {
+            MessagePart::PartB {
+                dimension_to_bow,
+                dimension_to_stern,
+                dimension_to_port,
+                dimension_to_starboard,
+                ..
+            } if self.is_auxiliary_craft() => Some(
+                (dimension_to_bow as u32) << 21
+                    | (dimension_to_stern as u32) << 12
+                    | (dimension_to_port as u32) << 6
+                    | dimension_to_starboard as u32,
+            ),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Eq)]
 /// Static Data Report messages have two different sub-message types.
 /// The idea is that both get broadcast periodically.
@@ -54,15 +83,136 @@ pub enum MessagePart {
     Unknown(u8),
 }
 
+/// Maximum number of reports a `StaticDataCombiner` holds while waiting for their
+/// other part
+pub const MAX_PENDING_PARTS: usize = 16;
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+type PendingParts = lib::std::vec::Vec<StaticDataReport>;
+#[cfg(all(not(feature = "std"), not(feature = "alloc")))]
+type PendingParts = lib::std::vec::Vec<StaticDataReport, MAX_PENDING_PARTS>;
+
+/// Part A and Part B of a Static Data Report from the same station, combined
+#[derive(Debug, PartialEq, Eq)]
+pub struct CombinedStaticDataReport {
+    pub mmsi: u32,
+    pub vessel_name: AsciiString,
+    pub ship_type: Option<ShipType>,
+    pub vendor_id: AsciiString,
+    pub model_serial: AsciiString,
+    pub unit_model_code: u8,
+    pub serial_number: u32,
+    pub callsign: AsciiString,
+    /// For auxiliary craft, the dimensions hold the parent ship's MMSI instead;
+    /// see `mothership_mmsi`
+    pub dimension_to_bow: u16,
+    pub dimension_to_stern: u16,
+    pub dimension_to_port: u16,
+    pub dimension_to_starboard: u16,
+    /// Only present for auxiliary craft
+    pub mothership_mmsi: Option<u32>,
+}
+
+impl CombinedStaticDataReport {
+    fn new(part_a: StaticDataReport, part_b: StaticDataReport) -> Option<Self> {
+        let mothership_mmsi = part_b.mothership_mmsi();
+        match (part_a.message_part, part_b.message_part) {
+            (
+                MessagePart::PartA { vessel_name },
+                MessagePart::PartB {
+                    ship_type,
+                    vendor_id,
+                    model_serial,
+                    unit_model_code,
+                    serial_number,
+                    callsign,
+                    dimension_to_bow,
+                    dimension_to_stern,
+                    dimension_to_port,
+                    dimension_to_starboard,
+                },
+            ) => Some(Self {
+                mmsi: part_a.mmsi,
+                vessel_name,
+                ship_type,
+                vendor_id,
+                model_serial,
+                unit_model_code,
+                serial_number,
+                callsign,
+                dimension_to_bow,
+                dimension_to_stern,
+                dimension_to_port,
+                dimension_to_starboard,
+                mothership_mmsi,
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// Pairs up Part A and Part B Static Data Reports, which Class B stations send
+/// separately, usually one right after the other. Parts are matched by MMSI, in
+/// either order. Reports waiting for their other part are held until it arrives,
+/// replaced by a newer report of the same part, or dropped, oldest first, once
+/// `MAX_PENDING_PARTS` stations are waiting.
+#[derive(Debug, Default)]
+pub struct StaticDataCombiner {
+    pending: PendingParts,
+}
+
+impl StaticDataCombiner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a report, returning the combined report if it completes a pair.
+    /// Reports of unknown parts are ignored.
+    pub fn push(&mut self, report: StaticDataReport) -> Option<CombinedStaticDataReport> {
+        let is_part_a = match report.message_part {
+            MessagePart::PartA { .. } => true,
+            MessagePart::PartB { .. } => false,
+            MessagePart::Unknown(_) => return None,
+        };
+        let pending = self
+            .pending
+            .iter()
+            .position(|pending| pending.mmsi == report.mmsi)
+            .map(|index| self.pending.remove(index));
+        match pending {
+            Some(pending)
+                if matches!(pending.message_part, MessagePart::PartA { .. }) != is_part_a =>
+            {
+                if is_part_a {
+                    CombinedStaticDataReport::new(report, pending)
+                } else {
+                    CombinedStaticDataReport::new(pending, report)
+                }
+            }
+            _ => {
+                if self.pending.len() == MAX_PENDING_PARTS {
+                    self.pending.remove(0);
+                }
+                #[cfg(any(feature = "std", feature = "alloc"))]
+                self.pending.push(report);
+                #[cfg(all(not(feature = "std"), not(feature = "alloc")))]
+                let _ = self.pending.push(report);
+                None
+            }
+        }
+    }
+}
+
 fn parse_message_part(data: (&[u8], usize)) -> IResult<(&[u8], usize), MessagePart> {
     let (data, part_number) = take_bits(2u8)(data)?;
     match part_number {
         0 => {
             // Part A
             let (data, vessel_name) = parse_6bit_ascii(data, 120)?;
-            // Senders occasionally skip sending the spare bits, so this is optional
+            // Part A is 168 bits, ending in 8 spare bits. Senders occasionally skip
+            // sending some or all of them, so this is optional
             let (data, _spare) =
-                take_bits::<_, u8, _, _>(lib::std::cmp::min(remaining_bits(data), 7))(data)?;
+                take_bits::<_, u8, _, _>(lib::std::cmp::min(remaining_bits(data), 8))(data)?;
             Ok((data, MessagePart::PartA { vessel_name }))
         }
         1 => {
@@ -123,6 +273,7 @@ fn parse_message(data: &[u8]) -> IResult<&[u8], StaticDataReport> {
 mod tests {
     #![allow(clippy::unreadable_literal)]
     use super::*;
+    use crate::messages::bit_writer::BitWriter;
 
     #[test]
     fn test_part_a_message() {
@@ -138,6 +289,28 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_part_a_full_spare() {
+        let mut message = BitWriter::new();
+        message
+            .u32(24, 6)
+            .u32(0, 2)
+            .u32(413996478, 30)
+            .u32(0, 2)
+            .string6("WAN SHUN HANG 6868", 120)
+            .u32(0, 8);
+        assert_eq!(message.bit_len(), 168);
+        let (data, _) = take_bits::<_, u64, _, ()>(38u8)((message.as_bytes(), 0)).unwrap();
+        let (data, part) = parse_message_part(data).unwrap();
+        assert_eq!(remaining_bits(data), 0);
+        assert_eq!(
+            part,
+            MessagePart::PartA {
+                vessel_name: "WAN SHUN HANG 6868".into()
+            }
+        );
+    }
+
     #[test]
     fn test_part_b_main_vessel_message() {
         let bytestream = b"H3mr@L4NC=D62?P<7nmpl00@8220";
@@ -185,4 +358,68 @@ mod tests {
             _ => panic!("Expected Message Part B"),
         }
     }
+
+    #[test]
+    fn test_mothership_mmsi() {
+        let mut message = BitWriter::new();
+        message
+            .u32(24, 6)
+            .u32(0, 2)
+            .u32(982575221, 30)
+            .u32(1, 2)
+            .u32(37, 8)
+            .string6("SRT", 18)
+            .u32(0, 4)
+            .u32(0, 20)
+            .string6("", 42)
+            .u32(257855600, 30)
+            .u32(0, 6);
+        let message = StaticDataReport::parse(message.as_bytes()).unwrap();
+        assert!(message.is_auxiliary_craft());
+        assert_eq!(message.mothership_mmsi(), Some(257855600));
+
+        let bitstream = crate::messages::unarmor(b"H3mr@L4NC=D62?P<7nmpl00@8220", 0).unwrap();
+        let message = StaticDataReport::parse(bitstream.as_ref()).unwrap();
+        assert!(!message.is_auxiliary_craft());
+        assert_eq!(message.mothership_mmsi(), None);
+    }
+
+    #[test]
+    fn test_combine_parts() {
+        let mut message = BitWriter::new();
+        message
+            .u32(24, 6)
+            .u32(0, 2)
+            .u32(257855600, 30)
+            .u32(0, 2)
+            .string6("SJOBRIS", 120)
+            .u32(0, 8);
+        let part_a = StaticDataReport::parse(message.as_bytes()).unwrap();
+        let bitstream = crate::messages::unarmor(b"H3mr@L4NC=D62?P<7nmpl00@8220", 0).unwrap();
+        let part_b = StaticDataReport::parse(bitstream.as_ref()).unwrap();
+        let mut message = BitWriter::new();
+        message
+            .u32(24, 6)
+            .u32(0, 2)
+            .u32(413996478, 30)
+            .u32(0, 2)
+            .string6("WAN SHUN HANG 6868", 120)
+            .u32(0, 8);
+        let other = StaticDataReport::parse(message.as_bytes()).unwrap();
+
+        let mut combiner = StaticDataCombiner::new();
+        assert_eq!(combiner.push(part_a), None);
+        assert_eq!(combiner.push(other), None);
+        let combined = combiner.push(part_b).unwrap();
+        assert_eq!(combined.mmsi, 257855600);
+        assert_eq!(combined.vessel_name, "SJOBRIS");
+        assert_eq!(combined.ship_type, Some(ShipType::Fishing));
+        assert_eq!(combined.callsign, "LG6584");
+        assert_eq!(combined.dimension_to_stern, 8);
+        assert_eq!(combined.mothership_mmsi, None);
+        // The pair is only reported once
+        let bitstream = crate::messages::unarmor(b"H3mr@L4NC=D62?P<7nmpl00@8220", 0).unwrap();
+        let part_b = StaticDataReport::parse(bitstream.as_ref()).unwrap();
+        assert_eq!(combiner.push(part_b), None);
+    }
 }
@@ -0,0 +1,310 @@
+//! NMEA 4.0 tag blocks, which carry metadata such as the receive time and station
+//! ahead of a sentence, for example `\s:2573345,c:1696241893*00\`
+use crate::errors::{Error, Result};
+use crate::lib;
+use crate::sentence::nmea_checksum;
+
+#[cfg(all(not(feature = "std"), not(feature = "alloc")))]
+const MAX_TEXT_SIZE_BYTES: usize = 64;
+/// Maximum number of tag blocks stacked ahead of a single sentence
+#[cfg(all(not(feature = "std"), not(feature = "alloc")))]
+pub const MAX_TAG_BLOCKS: usize = 4;
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub type TagBlockText = lib::std::string::String;
+#[cfg(all(not(feature = "std"), not(feature = "alloc")))]
+pub type TagBlockText = lib::std::string::String<MAX_TEXT_SIZE_BYTES>;
+
+/// Tag blocks ahead of a sentence, in the order they appear on the line
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub type TagBlocks = lib::std::vec::Vec<TagBlock>;
+#[cfg(all(not(feature = "std"), not(feature = "alloc")))]
+pub type TagBlocks = lib::std::vec::Vec<TagBlock, MAX_TAG_BLOCKS>;
+
+/// Ties together the sentences of a group, each with its own tag block, such as
+/// the fragments of a multi-sentence message (`g:1-2-1234`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TagBlockGroup {
+    /// Number of this sentence within the group, starting from 1
+    pub sentence_number: u8,
+    pub total_sentences: u8,
+    /// Identifies the group, among others from the same source
+    pub group_id: u32,
+}
+
+impl TagBlockGroup {
+    /// Parses the value of a `g:` field, such as `1-2-1234`
+    pub fn parse(value: &str) -> Result<Self> {
+        let mut parts = value.split('-');
+        let mut next = || {
+            parts
+                .next()
+                .ok_or_else(|| Error::from("Malformed tag block group"))
+        };
+        let group = Self {
+            sentence_number: parse_number(next()?)?,
+            total_sentences: parse_number(next()?)?,
+            group_id: parse_number(next()?)?,
+        };
+        if parts.next().is_some()
+            || group.sentence_number == 0
+            || group.sentence_number > group.total_sentences
+        {
+            return Err("Malformed tag block group".into());
+        }
+        Ok(group)
+    }
+}
+
+/// Fields of a tag block. Fields that aren't recognized are ignored.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct TagBlock {
+    /// UNIX time at which the sentence was received (`c:`)
+    pub receiver_timestamp: Option<u64>,
+    /// Station the sentence is destined for (`d:`)
+    pub destination_station: Option<TagBlockText>,
+    /// Line count (`n:`)
+    pub line_count: Option<u32>,
+    /// Relative time (`r:`)
+    pub relative_time: Option<u64>,
+    /// Station the sentence came from, usually the receiver (`s:`)
+    pub source_station: Option<TagBlockText>,
+    /// Free text (`t:`)
+    pub text: Option<TagBlockText>,
+    /// Sentence grouping (`g:`)
+    pub group: Option<TagBlockGroup>,
+}
+
+impl TagBlock {
+    /// Parses a tag block, with or without the enclosing backslashes. The checksum,
+    /// if any, is not verified, as many feeds don't fill it in; see
+    /// [`parse_checked()`](#method.parse_checked) to verify it.
+    pub fn parse(data: &[u8]) -> Result<Self> {
+        Self::parse_fields(data, false)
+    }
+
+    /// Parses a tag block like [`parse()`](#method.parse), but also requires the
+    /// checksum (`*hh`) to be present and to match the fields
+    pub fn parse_checked(data: &[u8]) -> Result<Self> {
+        Self::parse_fields(data, true)
+    }
+
+    fn parse_fields(data: &[u8], verify_checksum: bool) -> Result<Self> {
+        let data = data.strip_prefix(b"\\").unwrap_or(data);
+        let data = data.strip_suffix(b"\\").unwrap_or(data);
+        let data =
+            lib::std::str::from_utf8(data).map_err(|_| Error::from("Tag block is not ASCII"))?;
+        let fields = match data.split_once('*') {
+            Some((fields, checksum)) => {
+                if verify_checksum {
+                    check_checksum(fields, checksum)?;
+                }
+                fields
+            }
+            None if verify_checksum => return Err("Tag block has no checksum".into()),
+            None => data,
+        };
+        let mut tag_block = Self::default();
+        for field in fields.split(',') {
+            let (key, value) = field
+                .split_once(':')
+                .ok_or_else(|| Error::from("Malformed tag block field"))?;
+            match key {
+                "c" => tag_block.receiver_timestamp = Some(parse_number(value)?),
+                "d" => tag_block.destination_station = Some(parse_text(value)?),
+                "n" => tag_block.line_count = Some(parse_number(value)?),
+                "r" => tag_block.relative_time = Some(parse_number(value)?),
+                "s" => tag_block.source_station = Some(parse_text(value)?),
+                "t" => tag_block.text = Some(parse_text(value)?),
+                "g" => tag_block.group = Some(TagBlockGroup::parse(value)?),
+                _ => {}
+            }
+        }
+        Ok(tag_block)
+    }
+
+    /// The receiver timestamp (`c:`), in milliseconds. Feeds send this either in
+    /// seconds or in milliseconds, so values of 13 or more digits are taken to be in
+    /// milliseconds already, and shorter ones in seconds. In seconds, 13 digits would
+    /// be more than 30,000 years from now; in milliseconds, 12 digits would be
+    /// before September 2001.
+    pub fn timestamp_millis(&self) -> Option<u64> {
+        self.receiver_timestamp.map(|timestamp| {
+            if timestamp >= 1_000_000_000_000 {
+                timestamp
+            } else {
+                timestamp * 1000
+            }
+        })
+    }
+
+    /// Whether the source station (`s:`) suggests the sentence was received by
+    /// satellite rather than by a terrestrial station. There is no standard naming
+    /// for receivers, so this only checks for `sat` anywhere in the name, ignoring
+    /// case, as in `SAT-23` or `exactsat`; a feed that names its receivers
+    /// differently should check `source_station` itself.
+    ///
+    /// This is mostly of interest for Long Range AIS Broadcast messages (type 27),
+    /// which are sent for satellite reception but may also be picked up on shore.
+    pub fn is_satellite_source(&self) -> bool {
+        self.source_station.as_ref().is_some_and(|station| {
+            station
+                .as_bytes()
+                .windows(3)
+                .any(|window| window.eq_ignore_ascii_case(b"sat"))
+        })
+    }
+}
+
+/// Verifies the checksum of a tag block, which covers the fields before the `*`
+fn check_checksum(fields: &str, checksum: &str) -> Result<()> {
+    if checksum.len() != 2 || !checksum.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+        return Err("Malformed tag block checksum".into());
+    }
+    let expected = u8::from_str_radix(checksum, 16)
+        .map_err(|_| Error::from("Malformed tag block checksum"))?;
+    let found = nmea_checksum(fields.as_bytes());
+    if expected != found {
+        return Err(Error::Checksum { expected, found });
+    }
+    Ok(())
+}
+
+fn parse_number<T: lib::std::str::FromStr>(value: &str) -> Result<T> {
+    value
+        .parse()
+        .map_err(|_| Error::from("Invalid number in tag block"))
+}
+
+fn parse_text(value: &str) -> Result<TagBlockText> {
+    value
+        .parse()
+        .map_err(|_| Error::from("Tag block text too long"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_tag_block() {
+        let tag_block = TagBlock::parse(b"s:2573345,c:1696241893*00").unwrap();
+        assert_eq!(tag_block.source_station.unwrap(), "2573345");
+        assert_eq!(tag_block.receiver_timestamp, Some(1696241893));
+        assert_eq!(tag_block.destination_station, None);
+        assert_eq!(tag_block.line_count, None);
+    }
+
+    #[test]
+    fn parse_with_delimiters() {
+        let tag_block = TagBlock::parse(b"\\s:2573598,c:1720090996*00\\").unwrap();
+        assert_eq!(tag_block.source_station.unwrap(), "2573598");
+        assert_eq!(tag_block.receiver_timestamp, Some(1720090996));
+    }
+
+    #[cfg(all(not(feature = "std"), not(feature = "alloc")))]
+    #[test]
+    fn parse_text_too_long() {
+        let mut data = heapless::Vec::<u8, 80>::from_slice(b"t:").unwrap();
+        data.extend_from_slice(&[b'x'; MAX_TEXT_SIZE_BYTES + 1])
+            .unwrap();
+        assert_eq!(
+            TagBlock::parse(&data),
+            Err(Error::from("Tag block text too long"))
+        );
+    }
+
+    #[test]
+    fn parse_all_fields() {
+        let tag_block =
+            TagBlock::parse(b"c:1696241893,d:DEST,n:42,r:1000,s:rORBCOMM,t:hello,x:1*55").unwrap();
+        assert_eq!(tag_block.receiver_timestamp, Some(1696241893));
+        assert_eq!(tag_block.destination_station.unwrap(), "DEST");
+        assert_eq!(tag_block.line_count, Some(42));
+        assert_eq!(tag_block.relative_time, Some(1000));
+        assert_eq!(tag_block.source_station.unwrap(), "rORBCOMM");
+        assert_eq!(tag_block.text.unwrap(), "hello");
+    }
+
+    #[test]
+    fn timestamp_millis() {
+        let seconds = TagBlock::parse(b"c:1696241893*00").unwrap();
+        assert_eq!(seconds.receiver_timestamp, Some(1696241893));
+        assert_eq!(seconds.timestamp_millis(), Some(1696241893000));
+        let millis = TagBlock::parse(b"c:1696241893123*00").unwrap();
+        assert_eq!(millis.receiver_timestamp, Some(1696241893123));
+        assert_eq!(millis.timestamp_millis(), Some(1696241893123));
+        assert_eq!(
+            TagBlock::parse(b"s:2573345").unwrap().timestamp_millis(),
+            None
+        );
+    }
+
+    #[test]
+    fn satellite_source() {
+        assert!(TagBlock::parse(b"s:SAT-23*00")
+            .unwrap()
+            .is_satellite_source());
+        assert!(TagBlock::parse(b"s:exactsat*00")
+            .unwrap()
+            .is_satellite_source());
+        assert!(!TagBlock::parse(b"s:2573345*00")
+            .unwrap()
+            .is_satellite_source());
+        assert!(!TagBlock::parse(b"c:1696241893*00")
+            .unwrap()
+            .is_satellite_source());
+    }
+
+    #[test]
+    fn parse_checked() {
+        let tag_block = TagBlock::parse_checked(b"\\g:1-2-1234*5A\\").unwrap();
+        assert_eq!(tag_block.group.unwrap().group_id, 1234);
+        assert_eq!(
+            TagBlock::parse_checked(b"g:1-2-1234*00"),
+            Err(Error::Checksum {
+                expected: 0x00,
+                found: 0x5a,
+            })
+        );
+        assert_eq!(
+            TagBlock::parse_checked(b"g:1-2-1234"),
+            Err(Error::from("Tag block has no checksum"))
+        );
+        assert_eq!(
+            TagBlock::parse_checked(b"g:1-2-1234*5"),
+            Err(Error::from("Malformed tag block checksum"))
+        );
+        assert!(TagBlock::parse(b"g:1-2-1234*00").is_ok());
+    }
+
+    #[test]
+    fn parse_invalid() {
+        assert!(TagBlock::parse(b"c:soon*00").is_err());
+        assert!(TagBlock::parse(b"s2573345*00").is_err());
+    }
+
+    #[test]
+    fn parse_group() {
+        let tag_block = TagBlock::parse(b"g:1-2-1234,s:2573345*00").unwrap();
+        assert_eq!(
+            tag_block.group,
+            Some(TagBlockGroup {
+                sentence_number: 1,
+                total_sentences: 2,
+                group_id: 1234,
+            })
+        );
+        assert_eq!(tag_block.source_station.unwrap(), "2573345");
+        assert_eq!(TagBlock::parse(b"c:1696241893").unwrap().group, None);
+    }
+
+    #[test]
+    fn parse_invalid_group() {
+        for group in [
+            "1-2", "1-2-", "1-2-3-4", "a-2-1234", "3-2-1234", "0-2-1234", "",
+        ] {
+            assert!(TagBlockGroup::parse(group).is_err(), "group {:?}", group);
+        }
+    }
+}
@@ -32,6 +32,16 @@ impl EpfdType {
             _ => Some(Self::Unknown(data)),
         }
     }
+
+    /// Whether the position comes from a satellite navigation system (GPS, GLONASS,
+    /// Galileo, or a combination), rather than a terrestrial one, a surveyed
+    /// position, or an unspecified integrated system
+    pub fn is_satellite_based(&self) -> bool {
+        matches!(
+            self,
+            Self::Gps | Self::Glonass | Self::CombinedGpsAndGlonass | Self::Galileo
+        )
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Copy, Clone)]
@@ -272,3 +282,27 @@ impl AssignedMode {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn satellite_based_epfd() {
+        assert!(EpfdType::Gps.is_satellite_based());
+        assert!(EpfdType::Galileo.is_satellite_based());
+        assert!(EpfdType::CombinedGpsAndGlonass.is_satellite_based());
+        assert!(!EpfdType::Surveyed.is_satellite_based());
+        assert!(!EpfdType::LoranC.is_satellite_based());
+        assert!(!EpfdType::Unknown(9).is_satellite_based());
+    }
+
+    #[test]
+    fn reserved_epfd() {
+        for code in 9..=14 {
+            assert_eq!(EpfdType::parse(code), Some(EpfdType::Unknown(code)));
+        }
+        assert_eq!(EpfdType::parse(0), None);
+        assert_eq!(EpfdType::parse(15), None);
+    }
+}
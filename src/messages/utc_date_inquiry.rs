@@ -1,4 +1,5 @@
 //! UTC/Date Inquiry (type 10)
+use super::utc_date_response::UtcDateResponse;
 use super::AisMessageType;
 use crate::errors::Result;
 use nom::bits::{bits, complete::take as take_bits};
@@ -12,6 +13,14 @@ pub struct UtcDateInquiry {
     pub dest_mmsi: u32,
 }
 
+impl UtcDateInquiry {
+    /// Checks whether `response` was sent by the station this inquiry was addressed
+    /// to. Responses are not addressed, so this is a match on MMSI only.
+    pub fn is_answered_by(&self, response: &UtcDateResponse) -> bool {
+        self.dest_mmsi == response.mmsi
+    }
+}
+
 impl<'a> AisMessageType<'a> for UtcDateInquiry {
     fn name(&self) -> &'static str {
         "UTC/Date Inquiry"
@@ -71,4 +80,15 @@ mod tests {
         assert_eq!(report.mmsi, 440882000);
         assert_eq!(report.dest_mmsi, 366972000);
     }
+
+    #[test]
+    fn test_type10_answered_by_type11() {
+        let bitstream = crate::messages::unarmor(b":5MlU41GMK6@", 0).unwrap();
+        let inquiry = UtcDateInquiry::parse(bitstream.as_ref()).unwrap();
+        let bitstream = crate::messages::unarmor(b";03sl8AvA;5AO7gnf@<FdSA00000", 0).unwrap();
+        let mut response = UtcDateResponse::parse(bitstream.as_ref()).unwrap();
+        assert!(!inquiry.is_answered_by(&response));
+        response.mmsi = 366832740;
+        assert!(inquiry.is_answered_by(&response));
+    }
 }
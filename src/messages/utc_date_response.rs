@@ -3,7 +3,7 @@ use super::navigation::*;
 use super::parsers::*;
 use super::radio_status::{parse_radio, RadioStatus};
 use super::types::*;
-use super::AisMessageType;
+use super::{AisMessageType, CommonNavigation};
 use crate::errors::Result;
 use nom::bits::{bits, complete::take as take_bits};
 use nom::combinator::map;
@@ -39,6 +39,20 @@ impl<'a> AisMessageType<'a> for UtcDateResponse {
     }
 }
 
+impl CommonNavigation for UtcDateResponse {
+    fn mmsi(&self) -> u32 {
+        self.mmsi
+    }
+
+    fn longitude(&self) -> Option<f32> {
+        self.longitude
+    }
+
+    fn latitude(&self) -> Option<f32> {
+        self.latitude
+    }
+}
+
 fn parse_base(data: &[u8]) -> IResult<&[u8], UtcDateResponse> {
     bits(move |data| -> IResult<_, _> {
         let (data, message_type) = take_bits(6u8)(data)?;
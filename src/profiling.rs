@@ -0,0 +1,89 @@
+//! Decode timing statistics, for finding slow message parsers
+use crate::lib;
+use lib::std::collections::BTreeMap;
+use lib::std::time::Duration;
+
+/// Number of histogram buckets; the last one also counts anything slower
+pub const TIMING_BUCKETS: usize = 24;
+
+/// Decode times for one message type
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct DecodeTiming {
+    /// Number of messages decoded
+    pub count: u64,
+    /// Total time spent decoding
+    pub total: Duration,
+    /// Longest single decode
+    pub max: Duration,
+    /// Histogram of decode times. Bucket 0 counts decodes that took under a
+    /// microsecond, and bucket `i` those from 2<sup>i-1</sup> up to 2<sup>i</sup>
+    /// microseconds.
+    pub buckets: [u64; TIMING_BUCKETS],
+}
+
+impl DecodeTiming {
+    /// Average decode time, if anything has been decoded
+    pub fn mean(&self) -> Option<Duration> {
+        u32::try_from(self.count)
+            .ok()
+            .filter(|count| *count > 0)
+            .map(|count| self.total / count)
+    }
+
+    fn record(&mut self, elapsed: Duration) {
+        self.count += 1;
+        self.total += elapsed;
+        self.max = self.max.max(elapsed);
+        let micros = elapsed.as_micros();
+        let bucket = (u128::BITS - micros.leading_zeros()) as usize;
+        self.buckets[bucket.min(TIMING_BUCKETS - 1)] += 1;
+    }
+}
+
+/// Decode times, by message type, as collected by an
+/// [`AisParser`](../sentence/struct.AisParser.html)
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct TimingStats {
+    by_message_type: BTreeMap<u8, DecodeTiming>,
+}
+
+impl TimingStats {
+    /// Decode times for messages of type `message_type`, if any have been decoded
+    pub fn get(&self, message_type: u8) -> Option<&DecodeTiming> {
+        self.by_message_type.get(&message_type)
+    }
+
+    /// Decode times for each message type decoded so far, in message type order
+    pub fn iter(&self) -> impl Iterator<Item = (u8, &DecodeTiming)> {
+        self.by_message_type
+            .iter()
+            .map(|(message_type, timing)| (*message_type, timing))
+    }
+
+    pub(crate) fn record(&mut self, message_type: u8, elapsed: Duration) {
+        self.by_message_type
+            .entry(message_type)
+            .or_default()
+            .record(elapsed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn histogram() {
+        let mut stats = TimingStats::default();
+        stats.record(1, Duration::from_nanos(500));
+        stats.record(1, Duration::from_micros(3));
+        stats.record(1, Duration::from_secs(60));
+        let timing = stats.get(1).unwrap();
+        assert_eq!(timing.count, 3);
+        assert_eq!(timing.max, Duration::from_secs(60));
+        assert_eq!(timing.buckets[0], 1);
+        assert_eq!(timing.buckets[2], 1);
+        assert_eq!(timing.buckets[TIMING_BUCKETS - 1], 1);
+        assert_eq!(stats.get(5), None);
+    }
+}
@@ -3,22 +3,57 @@
 use crate::lib;
 
 use crate::errors::{Error, Result};
-use crate::messages::{self, AisMessage};
+use crate::messages::{
+    self,
+    navigation::BoundingBox,
+    tag_block::{TagBlock, TagBlocks},
+    AisMessage,
+};
+use lib::std::fmt::Write;
 use nom::branch::alt;
-use nom::bytes::complete::{tag, take, take_until};
+use nom::bytes::complete::{tag, take, take_till, take_until};
 use nom::character::complete::{anychar, digit1};
-use nom::combinator::{map, map_res, opt, peek, verify};
+use nom::combinator::{map, map_res, opt, peek, recognize, verify};
+use nom::multi::many0_count;
 use nom::number::complete::hex_u32;
 use nom::sequence::{delimited, terminated};
 use nom::IResult;
 
+#[cfg(any(feature = "std", feature = "alloc"))]
+use crate::lib::std::format;
+
 pub const MAX_SENTENCE_SIZE_BYTES: usize = 384;
+/// Maximum number of noise bytes that will be skipped ahead of the sentence
+/// (or tag block) start delimiter before giving up on the line
+pub const MAX_LEADING_JUNK_BYTES: usize = 32;
 
 #[cfg(any(feature = "std", feature = "alloc"))]
 pub type AisRawData = lib::std::vec::Vec<u8>;
 #[cfg(all(not(feature = "std"), not(feature = "alloc")))]
 pub type AisRawData = lib::std::vec::Vec<u8, MAX_SENTENCE_SIZE_BYTES>;
 
+/// Maximum number of fragments a message can be split into, as the fragment
+/// count is a single digit
+pub const MAX_FRAGMENTS: usize = 9;
+/// Longest possible NMEA sentence built by `AisSentenceBuilder`: a full payload,
+/// plus the delimiters, header fields, and checksum
+#[cfg(all(not(feature = "std"), not(feature = "alloc")))]
+const MAX_LINE_SIZE_BYTES: usize = MAX_SENTENCE_SIZE_BYTES + 32;
+
+/// Maximum number of fragmented messages that can be reassembled at once
+pub const MAX_FRAGMENT_ASSEMBLIES: usize = 4;
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+type FragmentAssemblies = lib::std::vec::Vec<FragmentAssembly>;
+#[cfg(all(not(feature = "std"), not(feature = "alloc")))]
+type FragmentAssemblies = lib::std::vec::Vec<FragmentAssembly, MAX_FRAGMENT_ASSEMBLIES>;
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub type SentenceLines = lib::std::vec::Vec<lib::std::string::String>;
+#[cfg(all(not(feature = "std"), not(feature = "alloc")))]
+pub type SentenceLines =
+    lib::std::vec::Vec<lib::std::string::String<MAX_LINE_SIZE_BYTES>, MAX_FRAGMENTS>;
+
 #[derive(PartialEq, Eq, Debug)]
 /// Represents the NMEA sentence type of an AIS message
 pub enum AisReportType {
@@ -84,17 +119,39 @@ impl<'a> From<&'a [u8]> for TalkerId {
     }
 }
 
+/// Kind of AIS station that sent a message
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub enum StationKind {
+    /// Base or shore station
+    BaseStation,
+    /// Aid to navigation
+    AidToNavigation,
+    /// Repeater station
+    Repeater,
+    /// Class A shipborne mobile station
+    ClassA,
+    /// Class B shipborne mobile station
+    ClassB,
+    /// Search and rescue aircraft
+    SarAircraft,
+    /// Not identifiable from the talker ID or message type
+    Unknown,
+}
+
 #[derive(Debug, PartialEq)]
 pub enum AisFragments {
     Complete(AisSentence),
     Incomplete(AisSentence),
+    /// A complete message reporting a position outside the area set with
+    /// [`AisParser::bbox_filter()`](struct.AisParser.html#method.bbox_filter)
+    Filtered(AisSentence),
 }
 
 impl From<AisFragments> for Option<AisSentence> {
     fn from(frag: AisFragments) -> Self {
         match frag {
             AisFragments::Complete(sentence) => Some(sentence),
-            AisFragments::Incomplete(_) => None,
+            AisFragments::Incomplete(_) | AisFragments::Filtered(_) => None,
         }
     }
 }
@@ -104,15 +161,116 @@ impl From<AisFragments> for Result<AisSentence> {
         match frag {
             AisFragments::Complete(sentence) => Ok(sentence),
             AisFragments::Incomplete(_) => Err("Incomplete message".into()),
+            AisFragments::Filtered(_) => Err("Message outside the bounding box".into()),
         }
     }
 }
 
-#[derive(Debug, Default)]
-pub struct AisParser {
+/// Fragments of a message received so far
+#[derive(Debug, Default, Clone)]
+struct FragmentAssembly {
+    channel: Option<char>,
     message_id: Option<u8>,
+    num_fragments: u8,
     fragment_number: u8,
     data: AisRawData,
+    /// Offset in `data` of the end of each fragment received so far
+    fragment_ends: [usize; MAX_FRAGMENTS],
+    /// Channel of a copy of this message received on another channel, and the
+    /// number of the last of its fragments found to be a duplicate
+    copy: Option<(Option<char>, u8)>,
+    /// Value of the parser's sentence count when the last fragment arrived
+    last_sentence: u64,
+}
+
+impl FragmentAssembly {
+    fn matches(&self, ais_sentence: &AisSentence) -> bool {
+        self.channel == ais_sentence.channel && self.message_id == ais_sentence.message_id
+    }
+
+    /// Returns the payload of fragment `fragment_number`, if it has been received
+    fn fragment(&self, fragment_number: u8) -> Option<&[u8]> {
+        let index = (fragment_number as usize).checked_sub(1)?;
+        if fragment_number > self.fragment_number {
+            return None;
+        }
+        let end = *self.fragment_ends.get(index)?;
+        let start = match index {
+            0 => 0,
+            _ => self.fragment_ends[index - 1],
+        };
+        Some(&self.data[start..end])
+    }
+
+    /// Checks whether `ais_sentence` is a fragment of this message received on
+    /// another channel. The fragment's payload must match the one received here; if
+    /// that hasn't arrived yet, the fragment must follow on from earlier duplicates.
+    fn is_copy(&self, ais_sentence: &AisSentence) -> bool {
+        self.channel != ais_sentence.channel
+            && self.message_id == ais_sentence.message_id
+            && self.num_fragments == ais_sentence.num_fragments
+            && match self.fragment(ais_sentence.fragment_number) {
+                Some(fragment) => fragment == &ais_sentence.data[..],
+                None => matches!(
+                    self.copy,
+                    Some((channel, fragment_number))
+                        if channel == ais_sentence.channel
+                            && fragment_number + 1 == ais_sentence.fragment_number
+                ),
+            }
+    }
+}
+
+/// Custom message handlers, by message type
+#[cfg(any(feature = "std", feature = "alloc"))]
+#[derive(Default)]
+struct Handlers(lib::std::collections::BTreeMap<u8, messages::custom::MessageHandler>);
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl lib::std::fmt::Debug for Handlers {
+    fn fmt(&self, f: &mut lib::std::fmt::Formatter<'_>) -> lib::std::fmt::Result {
+        f.debug_set().entries(self.0.keys()).finish()
+    }
+}
+
+/// Called when an incomplete set of fragments is dropped. Without an allocator,
+/// this can only be a function pointer.
+#[cfg(any(feature = "std", feature = "alloc"))]
+struct DroppedFragmentsCallback(lib::std::boxed::Box<dyn FnMut(Option<u8>, u8) + Send + Sync>);
+#[cfg(all(not(feature = "std"), not(feature = "alloc")))]
+struct DroppedFragmentsCallback(fn(Option<u8>, u8));
+
+impl DroppedFragmentsCallback {
+    fn call(&mut self, assembly: &FragmentAssembly) {
+        (self.0)(assembly.message_id, assembly.fragment_number)
+    }
+}
+
+impl lib::std::fmt::Debug for DroppedFragmentsCallback {
+    fn fmt(&self, f: &mut lib::std::fmt::Formatter<'_>) -> lib::std::fmt::Result {
+        f.write_str("DroppedFragmentsCallback")
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct AisParser {
+    /// Messages being reassembled, oldest first
+    assemblies: FragmentAssemblies,
+    /// Recently completed fragmented messages, oldest first, kept until their copy
+    /// on another channel has been seen
+    completed: FragmentAssemblies,
+    /// Number of sentences parsed so far
+    sentences: u64,
+    fragment_timeout: Option<u64>,
+    strict: bool,
+    normalize_names: bool,
+    on_dropped_fragments: Option<DroppedFragmentsCallback>,
+    bbox: Option<BoundingBox>,
+    sequence: Option<u64>,
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    handlers: Handlers,
+    #[cfg(feature = "profiling")]
+    timing: crate::profiling::TimingStats,
 }
 
 impl AisParser {
@@ -121,59 +279,408 @@ impl AisParser {
         Self::default()
     }
 
+    /// Creates a new `AisParser` instance, which drops incomplete fragmented messages
+    /// once `fragment_timeout` more sentences have been received without another of
+    /// their fragments arriving. Otherwise, a message whose final fragment is lost is
+    /// only dropped once its channel and message ID are reused, or to make room for
+    /// other messages. Dropped messages are reported to
+    /// [`on_dropped_fragments()`](#method.on_dropped_fragments).
+    pub fn with_timeout(fragment_timeout: u64) -> Self {
+        Self {
+            fragment_timeout: Some(fragment_timeout),
+            ..Self::default()
+        }
+    }
+
+    /// Discards all fragments received so far, without reporting them as dropped.
+    /// Settings are kept, and sequence numbers continue to count up.
+    pub fn reset(&mut self) {
+        self.assemblies.clear();
+        self.completed.clear();
+    }
+
+    /// Enables or disables strict mode. By default, fields with unknown or reserved
+    /// values are passed through; in strict mode, decoded messages containing any
+    /// such values are rejected with an error instead. Strict mode also verifies the
+    /// checksums of tag blocks, which are otherwise ignored, as many feeds don't fill
+    /// them in.
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Enables or disables name normalization. When enabled, vessel names, callsigns,
+    /// and destinations in decoded messages are cleaned up using
+    /// [`normalize_name()`](../messages/fn.normalize_name.html).
+    pub fn normalize_names(mut self, normalize_names: bool) -> Self {
+        self.normalize_names = normalize_names;
+        self
+    }
+
+    /// Sets a function to be called whenever an incomplete set of fragments is
+    /// dropped, either because its first fragment arrived again before it was
+    /// completed, or to make room for another message once `MAX_FRAGMENT_ASSEMBLIES`
+    /// are being reassembled. The function is passed the sequential message ID of
+    /// the dropped set, and the number of fragments that had been received.
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    pub fn on_dropped_fragments<F>(mut self, callback: F) -> Self
+    where
+        F: FnMut(Option<u8>, u8) + Send + Sync + 'static,
+    {
+        self.on_dropped_fragments = Some(DroppedFragmentsCallback(lib::std::boxed::Box::new(
+            callback,
+        )));
+        self
+    }
+
+    /// Sets a function to be called whenever an incomplete set of fragments is
+    /// dropped, either because its first fragment arrived again before it was
+    /// completed, or to make room for another message once `MAX_FRAGMENT_ASSEMBLIES`
+    /// are being reassembled. The function is passed the sequential message ID of
+    /// the dropped set, and the number of fragments that had been received. Without
+    /// an allocator, closures that capture state can't be used.
+    #[cfg(all(not(feature = "std"), not(feature = "alloc")))]
+    pub fn on_dropped_fragments(mut self, callback: fn(Option<u8>, u8)) -> Self {
+        self.on_dropped_fragments = Some(DroppedFragmentsCallback(callback));
+        self
+    }
+
+    /// Drops decoded messages reporting a position outside the given area. Messages
+    /// without a position, or whose position is not available, are kept. See
+    /// [`parse()`](#method.parse) for how dropped messages are returned.
+    pub fn bbox_filter(
+        mut self,
+        min_latitude: f32,
+        min_longitude: f32,
+        max_latitude: f32,
+        max_longitude: f32,
+    ) -> Self {
+        self.bbox = Some(BoundingBox {
+            min_latitude,
+            min_longitude,
+            max_latitude,
+            max_longitude,
+        });
+        self
+    }
+
+    /// Enables or disables sequence numbers. When enabled, each complete sentence
+    /// returned by [`parse()`](#method.parse) is numbered, counting up from 0, in its
+    /// `sequence` field. Messages dropped by [`bbox_filter()`](#method.bbox_filter)
+    /// are not numbered. Unlike the sequential message ID of fragments, this never
+    /// wraps in practice, so it can be used to order messages and spot gaps.
+    pub fn sequence_numbers(mut self, sequence_numbers: bool) -> Self {
+        self.sequence = sequence_numbers.then_some(0);
+        self
+    }
+
+    /// Decodes messages of type `message_type` with `handler`, rather than the
+    /// built-in decoding, if any. This can be used for message types this crate
+    /// doesn't support, or to replace its decoding of a type. Decoded messages are
+    /// returned as `AisMessage::Custom`. Registering another handler for the same
+    /// type replaces the first.
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    pub fn register_handler(
+        mut self,
+        message_type: u8,
+        handler: messages::custom::MessageHandler,
+    ) -> Self {
+        self.handlers.0.insert(message_type, handler);
+        self
+    }
+
+    /// Returns the time taken to decode messages so far, by message type. Only
+    /// messages that were decoded successfully are counted.
+    #[cfg(feature = "profiling")]
+    pub fn timing_stats(&self) -> &crate::profiling::TimingStats {
+        &self.timing
+    }
+
     /// Parses `line` as an NMEA sentence, checking the checksum and returning an
     /// an `AisSentence`. Note that several `AisSentence`s might be required to
     /// complete a message, if they are fragments
     /// If `decode` is `true`, the internal AIS message will also be parsed
     /// If it is `false`, then internal AIS messages will be ignored.
     /// In both cases, AIS data will be passed along raw.
-    pub fn parse(&mut self, line: &[u8], decode: bool) -> Result<AisFragments> {
-        let (_, (data, mut ais_sentence, checksum)) = parse_nmea_sentence(line)?;
+    ///
+    /// Fragmented messages are reassembled independently for each channel and
+    /// sequential message ID, so fragments of up to `MAX_FRAGMENT_ASSEMBLIES`
+    /// messages may be interleaved; beyond that, the oldest incomplete message is
+    /// dropped. When a fragmented message is received on both AIS channels, only the
+    /// copy whose first fragment arrived first is reassembled. Fragments on the other
+    /// channel with the same message ID, fragment number, and payload as that copy are
+    /// duplicates, so are returned as `AisFragments::Incomplete`, and never complete.
+    ///
+    /// Tag blocks are only accepted ahead of the sentence; a line with a tag block
+    /// after the sentence is rejected with an error.
+    ///
+    /// Messages dropped by [`bbox_filter()`](#method.bbox_filter) are returned as
+    /// `AisFragments::Filtered`, rather than as complete sentences.
+    ///
+    /// Any tag blocks ahead of the sentence are returned alongside it, in the order
+    /// they appear on the line. Stacked blocks are kept separate, so each block's
+    /// fields are available even where several set the same one.
+    pub fn parse(&mut self, line: &[u8], decode: bool) -> Result<(TagBlocks, AisFragments)> {
+        let (line, raw_tag_blocks) = parse_tag_blocks(line)?;
+        let mut tag_blocks = TagBlocks::new();
+        for raw_tag_block in raw_tag_blocks
+            .split(|&byte| byte == b'\\')
+            .filter(|raw_tag_block| !raw_tag_block.is_empty())
+        {
+            let tag_block = if self.strict {
+                TagBlock::parse_checked(raw_tag_block)?
+            } else {
+                TagBlock::parse(raw_tag_block)?
+            };
+            #[cfg(any(feature = "std", feature = "alloc"))]
+            tag_blocks.push(tag_block);
+            #[cfg(all(not(feature = "std"), not(feature = "alloc")))]
+            tag_blocks
+                .push(tag_block)
+                .map_err(|_| Error::from("Too many tag blocks"))?;
+        }
+        let fragments = self.parse_sentence(line, decode)?;
+        Ok((tag_blocks, fragments))
+    }
+
+    fn parse_sentence(&mut self, line: &[u8], decode: bool) -> Result<AisFragments> {
+        let (remaining, (data, mut ais_sentence, checksum)) = parse_nmea_sentence(line)?;
         Self::check_checksum(data, checksum)?;
+        // Some feeds append the tag block; rather than ignore it, refuse the line
+        if remaining.trim_ascii_start().starts_with(b"\\") {
+            return Err("Tag block follows the sentence, rather than preceding it".into());
+        }
+        if ais_sentence.is_fragment() && ais_sentence.message_id.is_none() {
+            return Err("Fragment of a multi-sentence message has no sequential message ID".into());
+        }
+        self.sentences += 1;
+        self.drop_stale_fragments();
+        if self.check_duplicate_fragment(&ais_sentence) {
+            return Ok(AisFragments::Incomplete(ais_sentence));
+        }
         if ais_sentence.has_more() {
             if ais_sentence.fragment_number == 1 {
-                self.message_id = ais_sentence.message_id;
-                self.fragment_number = 0;
-                self.data = AisRawData::default();
+                self.start_assembly(&ais_sentence)?;
             }
             self.verify_and_extend_data(&ais_sentence)?;
             Ok(AisFragments::Incomplete(ais_sentence))
         } else {
             if ais_sentence.is_fragment() {
-                self.verify_and_extend_data(&ais_sentence)?;
-                let mut data = AisRawData::default();
-                lib::std::mem::swap(&mut data, &mut self.data);
-                ais_sentence.data = data;
+                let index = self.verify_and_extend_data(&ais_sentence)?;
+                let assembly = self.assemblies.remove(index);
+                ais_sentence.data = assembly.data.clone();
+                self.add_completed(assembly);
+                // Later fragments don't start with the message type
+                let (_, message_type) = parse_message_type(&ais_sentence.data)?;
+                ais_sentence.message_type = message_type;
             }
             if decode {
                 let unarmored =
                     messages::unarmor(&ais_sentence.data, ais_sentence.fill_bit_count as usize)?;
-                ais_sentence.message = Some(messages::parse(&unarmored)?)
+                #[cfg(feature = "profiling")]
+                let start = lib::std::time::Instant::now();
+                #[cfg(any(feature = "std", feature = "alloc"))]
+                let mut message = match self.handlers.0.get(&ais_sentence.message_type) {
+                    Some(handler) => {
+                        AisMessage::Custom(messages::custom::decode(handler, &unarmored)?)
+                    }
+                    None => messages::parse(&unarmored)?,
+                };
+                #[cfg(all(not(feature = "std"), not(feature = "alloc")))]
+                let mut message = messages::parse(&unarmored)?;
+                #[cfg(feature = "profiling")]
+                self.timing
+                    .record(ais_sentence.message_type, start.elapsed());
+                if self.normalize_names {
+                    message.normalize_names();
+                }
+                if self.strict {
+                    Self::check_spec(&message)?;
+                }
+                let in_bbox = self.in_bbox(&message);
+                ais_sentence.message = Some(message);
+                if !in_bbox {
+                    return Ok(AisFragments::Filtered(ais_sentence));
+                }
+            }
+            if let Some(sequence) = self.sequence.as_mut() {
+                ais_sentence.sequence = Some(*sequence);
+                *sequence += 1;
             }
             Ok(AisFragments::Complete(ais_sentence))
         }
     }
 
-    fn verify_and_extend_data(&mut self, ais_sentence: &AisSentence) -> Result<()> {
-        if self.message_id != ais_sentence.message_id {
-            return Err("Message ID out of sequence".into());
+    /// Checks whether `ais_sentence` is a duplicate of a fragment of a message being
+    /// reassembled (or the most recently completed one), received on another channel,
+    /// and if so, notes that it was seen. Message IDs only run from 0 to 9 for each
+    /// station, so they often repeat across channels; only the payload shows whether
+    /// the fragment belongs to the same message.
+    fn check_duplicate_fragment(&mut self, ais_sentence: &AisSentence) -> bool {
+        if !ais_sentence.is_fragment()
+            || self
+                .assemblies
+                .iter()
+                .any(|assembly| assembly.matches(ais_sentence))
+        {
+            return false;
+        }
+        let copy = Some((ais_sentence.channel, ais_sentence.fragment_number));
+        if let Some(original) = self
+            .assemblies
+            .iter_mut()
+            .find(|assembly| assembly.is_copy(ais_sentence))
+        {
+            original.copy = copy;
+            return true;
+        }
+        match self
+            .completed
+            .iter()
+            .position(|completed| completed.is_copy(ais_sentence))
+        {
+            Some(index) => {
+                // Once its copy is complete too, the message can't be duplicated again
+                if ais_sentence.has_more() {
+                    self.completed[index].copy = copy;
+                } else {
+                    self.completed.remove(index);
+                }
+                true
+            }
+            None => false,
         }
-        if ais_sentence.fragment_number - self.fragment_number != 1 {
+    }
+
+    /// Keeps a completed message, to spot its copy on another channel. It replaces
+    /// any earlier message with the same channel and message ID, or otherwise the
+    /// oldest, if too many are kept.
+    fn add_completed(&mut self, assembly: FragmentAssembly) {
+        match self.completed.iter().position(|completed| {
+            completed.channel == assembly.channel && completed.message_id == assembly.message_id
+        }) {
+            Some(index) => {
+                self.completed.remove(index);
+            }
+            None if self.completed.len() >= MAX_FRAGMENT_ASSEMBLIES => {
+                self.completed.remove(0);
+            }
+            None => {}
+        }
+        #[cfg(any(feature = "std", feature = "alloc"))]
+        self.completed.push(assembly);
+        // There is always room, having removed a message if needed
+        #[cfg(all(not(feature = "std"), not(feature = "alloc")))]
+        let _ = self.completed.push(assembly);
+    }
+
+    /// Drops fragments that have timed out, if `fragment_timeout` is set
+    fn drop_stale_fragments(&mut self) {
+        let timeout = match self.fragment_timeout {
+            Some(timeout) => timeout,
+            None => return,
+        };
+        let sentences = self.sentences;
+        let callback = &mut self.on_dropped_fragments;
+        self.assemblies.retain(|assembly| {
+            let stale = sentences - assembly.last_sentence > timeout;
+            if let (true, Some(callback)) = (stale, callback.as_mut()) {
+                callback.call(assembly);
+            }
+            !stale
+        });
+        self.completed
+            .retain(|completed| sentences - completed.last_sentence <= timeout);
+    }
+
+    /// Starts reassembling a new message from its first fragment, replacing any
+    /// earlier fragments with the same channel and message ID, or otherwise the
+    /// oldest message if too many are being reassembled
+    fn start_assembly(&mut self, ais_sentence: &AisSentence) -> Result<()> {
+        let dropped = match self
+            .assemblies
+            .iter()
+            .position(|assembly| assembly.matches(ais_sentence))
+        {
+            Some(index) => Some(self.assemblies.remove(index)),
+            None if self.assemblies.len() >= MAX_FRAGMENT_ASSEMBLIES => {
+                Some(self.assemblies.remove(0))
+            }
+            None => None,
+        };
+        if let (Some(callback), Some(dropped)) = (self.on_dropped_fragments.as_mut(), dropped) {
+            callback.call(&dropped);
+        }
+        let assembly = FragmentAssembly {
+            channel: ais_sentence.channel,
+            message_id: ais_sentence.message_id,
+            num_fragments: ais_sentence.num_fragments,
+            ..Default::default()
+        };
+        #[cfg(any(feature = "std", feature = "alloc"))]
+        self.assemblies.push(assembly);
+        #[cfg(all(not(feature = "std"), not(feature = "alloc")))]
+        self.assemblies
+            .push(assembly)
+            .map_err(|_| Error::from("Too many fragmented messages"))?;
+        Ok(())
+    }
+
+    fn in_bbox(&self, message: &AisMessage) -> bool {
+        match (self.bbox, message.position()) {
+            (Some(bbox), Some((latitude, longitude))) => bbox.contains(latitude, longitude),
+            _ => true,
+        }
+    }
+
+    /// Adds a fragment to the message it belongs to, returning the index of that
+    /// message in `assemblies`
+    fn verify_and_extend_data(&mut self, ais_sentence: &AisSentence) -> Result<usize> {
+        let index = self
+            .assemblies
+            .iter()
+            .position(|assembly| assembly.matches(ais_sentence))
+            .ok_or_else(|| Error::from("Message ID out of sequence"))?;
+        let assembly = &mut self.assemblies[index];
+        if ais_sentence
+            .fragment_number
+            .wrapping_sub(assembly.fragment_number)
+            != 1
+        {
             return Err("Fragment numbers out of sequence".into());
         }
-        self.fragment_number = ais_sentence.fragment_number;
+        assembly.fragment_number = ais_sentence.fragment_number;
+        assembly.last_sentence = self.sentences;
         #[cfg(any(feature = "std", feature = "alloc"))]
-        self.data.extend_from_slice(&ais_sentence.data);
+        assembly.data.extend_from_slice(&ais_sentence.data);
         #[cfg(all(not(feature = "std"), not(feature = "alloc")))]
-        self.data
+        assembly
+            .data
             .extend_from_slice(&ais_sentence.data)
             .map_err(|_| Error::from("Vec is full on extend_from_slice"))?;
-        Ok(())
+        if let Some(end) = assembly
+            .fragment_ends
+            .get_mut(ais_sentence.fragment_number as usize - 1)
+        {
+            *end = assembly.data.len();
+        }
+        Ok(index)
+    }
+
+    /// Rejects messages with fields holding unknown or reserved values
+    fn check_spec(message: &AisMessage) -> Result<()> {
+        match message.out_of_spec_field() {
+            #[cfg(any(feature = "std", feature = "alloc"))]
+            Some(field) => Err(format!("Out of spec value in field: {}", field).into()),
+            #[cfg(all(not(feature = "std"), not(feature = "alloc")))]
+            Some(_) => Err("Out of spec field value".into()),
+            None => Ok(()),
+        }
     }
 
     /// Verifies the AIS sentence checksum
     fn check_checksum(sentence: &[u8], expected_checksum: u8) -> Result<u8> {
-        let received_checksum = sentence.iter().fold(0u8, |acc, &item| acc ^ item);
+        let received_checksum = nmea_checksum(sentence);
         if expected_checksum != received_checksum {
             Err(Error::Checksum {
                 expected: expected_checksum,
@@ -185,6 +692,134 @@ impl AisParser {
     }
 }
 
+/// Decodes newline-separated NMEA sentences from `text`, reassembling fragmented
+/// messages, and yielding each complete message in turn. Blank lines are skipped.
+///
+/// # Example
+/// ```
+/// use ais::messages::AisMessage;
+///
+/// let text = "!AIVDM,1,1,,A,13u?etPv2;0n:dDPwUM1U1Cb069D,0*24
+/// !AIVDM,1,1,,A,B6:hQDh0029Pt<4TAS003h6TSP00,0*26";
+/// let mut messages = ais::decode_lines(text);
+/// assert!(matches!(messages.next(), Some(Ok(AisMessage::PositionReport(_)))));
+/// assert!(matches!(
+///     messages.next(),
+///     Some(Ok(AisMessage::StandardClassBPositionReport(_)))
+/// ));
+/// assert!(messages.next().is_none());
+/// ```
+pub fn decode_lines(text: &str) -> impl Iterator<Item = Result<AisMessage>> + '_ {
+    let mut parser = AisParser::new();
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .filter_map(move |line| match parser.parse(line.as_bytes(), true) {
+            Ok((_, AisFragments::Complete(sentence))) => sentence.message.map(Ok),
+            Ok((_, AisFragments::Incomplete(_) | AisFragments::Filtered(_))) => None,
+            Err(err) => Some(Err(err)),
+        })
+}
+
+/// A decoded message, along with the sentence it arrived in and any tag blocks
+/// ahead of it
+#[derive(Debug, PartialEq)]
+pub struct DecodedMessage {
+    pub tag_blocks: TagBlocks,
+    /// The sentence the message was decoded from. Its `message` field is `None`,
+    /// as the message itself is moved to `message`.
+    pub sentence: AisSentence,
+    pub message: AisMessage,
+}
+
+/// Parses and decodes a single NMEA sentence, returning the message together with
+/// the sentence and tag blocks it came from.
+///
+/// Only single-fragment messages can be decoded this way; a fragment of a longer
+/// message is reported as an error. Use [`AisParser`] to reassemble those.
+///
+/// ```
+/// use ais::messages::AisMessage;
+///
+/// let line = b"\\s:2573345,c:1696241893*00\\!AIVDM,1,1,,A,13u?etPv2;0n:dDPwUM1U1Cb069D,0*24";
+/// let decoded = ais::decode_with_metadata(line)?;
+/// assert_eq!(decoded.tag_blocks[0].receiver_timestamp, Some(1696241893));
+/// assert_eq!(decoded.sentence.channel, Some('A'));
+/// assert!(matches!(decoded.message, AisMessage::PositionReport(_)));
+/// # Ok::<(), ais::errors::Error>(())
+/// ```
+pub fn decode_with_metadata(line: &[u8]) -> Result<DecodedMessage> {
+    let (tag_blocks, fragments) = AisParser::new().parse(line, true)?;
+    let mut sentence = Result::<AisSentence>::from(fragments)?;
+    let message = sentence
+        .message
+        .take()
+        .ok_or_else(|| Error::from("No message decoded"))?;
+    Ok(DecodedMessage {
+        tag_blocks,
+        sentence,
+        message,
+    })
+}
+
+/// A complete sentence, with any tag blocks ahead of it
+pub type TaggedSentence = (TagBlocks, AisSentence);
+
+/// Reads the file at `path`, and decodes the NMEA sentences in it, one per line,
+/// reassembling fragmented messages. Each complete sentence is returned with its
+/// tag blocks, if any. Lines that can't be parsed don't stop the rest of the file
+/// being decoded; their errors are returned in place of a sentence. Blank lines are
+/// skipped.
+///
+/// Returns an error only if the file can't be read.
+#[cfg(feature = "std")]
+pub fn decode_file_to_vec<P: AsRef<std::path::Path>>(
+    path: P,
+) -> Result<lib::std::vec::Vec<Result<TaggedSentence>>> {
+    let data = std::fs::read(path.as_ref())
+        .map_err(|err| Error::from(format!("Unable to read {:?}: {}", path.as_ref(), err)))?;
+    let mut parser = AisParser::new();
+    Ok(data
+        .split(|&byte| byte == b'\n')
+        .filter_map(|line| decode_line(&mut parser, line))
+        .collect())
+}
+
+/// Reads lines from `reader`, and decodes the NMEA sentences in them with `parser`,
+/// reassembling fragmented messages. Tag blocks are handled as for
+/// [`decode_file_to_vec()`](fn.decode_file_to_vec.html), but only the sentences are
+/// returned. Lines that can't be parsed, and read errors, are returned in place of a
+/// sentence; reading stops at the end of `reader`. Blank lines are skipped.
+#[cfg(feature = "std")]
+pub fn decode_reader<'a, R: std::io::Read + 'a>(
+    reader: R,
+    parser: &'a mut AisParser,
+) -> impl Iterator<Item = Result<AisSentence>> + 'a {
+    use std::io::BufRead;
+    std::io::BufReader::new(reader)
+        .split(b'\n')
+        .filter_map(move |line| match line {
+            Ok(line) => decode_line(parser, &line),
+            Err(err) => Some(Err(Error::from(format!("Unable to read line: {}", err)))),
+        })
+        .map(|result| result.map(|(_, sentence)| sentence))
+}
+
+/// Decodes one line of a file or stream, returning nothing for blank lines and
+/// fragments of incomplete messages
+#[cfg(feature = "std")]
+fn decode_line(parser: &mut AisParser, line: &[u8]) -> Option<Result<TaggedSentence>> {
+    let line = line.trim_ascii();
+    if line.is_empty() {
+        return None;
+    }
+    match parser.parse(line, true) {
+        Ok((tag_blocks, AisFragments::Complete(sentence))) => Some(Ok((tag_blocks, sentence))),
+        Ok((_, AisFragments::Incomplete(_) | AisFragments::Filtered(_))) => None,
+        Err(err) => Some(Err(err)),
+    }
+}
+
 #[derive(Debug, PartialEq)]
 /// Represents an NMEA sentence parsed as AIS
 pub struct AisSentence {
@@ -196,8 +831,13 @@ pub struct AisSentence {
     pub channel: Option<char>,
     pub data: AisRawData,
     pub fill_bit_count: u8,
+    /// Message type, from the first 6-bit symbol of the payload. For fragmented
+    /// messages, this is only meaningful on the first and the completed sentence.
     pub message_type: u8,
     pub message: Option<AisMessage>,
+    /// Sequence number of this sentence, if enabled with
+    /// [`AisParser::sequence_numbers()`](struct.AisParser.html#method.sequence_numbers)
+    pub sequence: Option<u64>,
 }
 
 impl AisSentence {
@@ -210,6 +850,168 @@ impl AisSentence {
     pub fn is_fragment(&self) -> bool {
         self.num_fragments != 1
     }
+
+    /// Returns the kind of station that sent this sentence. Talker IDs specific to
+    /// a kind of station take precedence; otherwise, such as for the generic `AI`
+    /// talker, the kind is inferred from the message type.
+    pub fn source_station_kind(&self) -> StationKind {
+        match self.talker_id {
+            TalkerId::AN => StationKind::AidToNavigation,
+            TalkerId::AB | TalkerId::AD | TalkerId::AS | TalkerId::BS | TalkerId::SA => {
+                StationKind::BaseStation
+            }
+            TalkerId::AX => StationKind::Repeater,
+            _ => match self.message_type {
+                1..=3 | 5 => StationKind::ClassA,
+                4 | 16 | 17 | 20 | 22 | 23 => StationKind::BaseStation,
+                9 => StationKind::SarAircraft,
+                18 | 19 | 24 => StationKind::ClassB,
+                21 => StationKind::AidToNavigation,
+                _ => StationKind::Unknown,
+            },
+        }
+    }
+}
+
+/// Builds NMEA sentences from armored AIS payloads, for transmission. Payloads
+/// longer than the maximum payload size are split into fragments, which share a
+/// sequential message ID.
+///
+/// # Example
+/// ```
+/// use ais::sentence::{AisSentenceBuilder, TalkerId};
+///
+/// let mut builder = AisSentenceBuilder::new(TalkerId::AI, Some('A'));
+/// let lines = builder.build_sentence(b"13u?etPv2;0n:dDPwUM1U1Cb069D", 0)?;
+/// assert_eq!(lines[0], "!AIVDM,1,1,,A,13u?etPv2;0n:dDPwUM1U1Cb069D,0*24");
+/// # Ok::<(), ais::errors::Error>(())
+/// ```
+#[derive(Debug)]
+pub struct AisSentenceBuilder {
+    talker_id: TalkerId,
+    report_type: AisReportType,
+    channel: Option<char>,
+    max_payload_size: usize,
+    message_id: u8,
+}
+
+impl AisSentenceBuilder {
+    /// Creates a new `AisSentenceBuilder`, which builds `VDM` sentences from the given
+    /// talker, on the given channel
+    pub fn new(talker_id: TalkerId, channel: Option<char>) -> Self {
+        Self {
+            talker_id,
+            report_type: AisReportType::VDM,
+            channel,
+            max_payload_size: MAX_SENTENCE_SIZE_BYTES,
+            message_id: 0,
+        }
+    }
+
+    /// Sets the sentence type; `VDO` is used for reports from own ship
+    pub fn report_type(mut self, report_type: AisReportType) -> Self {
+        self.report_type = report_type;
+        self
+    }
+
+    /// Sets the largest payload, in armored bytes, carried by a single sentence.
+    /// Defaults to `MAX_SENTENCE_SIZE_BYTES`; a size of 60 keeps sentences within the
+    /// 82 characters allowed by NMEA 0183.
+    pub fn max_payload_size(mut self, max_payload_size: usize) -> Self {
+        self.max_payload_size = max_payload_size;
+        self
+    }
+
+    /// Builds the NMEA sentences carrying `data`, an armored payload (see
+    /// [`armor()`](../messages/fn.armor.html)), with `fill_bits` padding bits at the end.
+    /// The lines have no line endings. Fragmented messages are assigned sequential
+    /// message IDs from 0 to 9, which wrap around.
+    pub fn build_sentence(&mut self, data: &[u8], fill_bits: u8) -> Result<SentenceLines> {
+        let talker_id = match self.talker_id {
+            TalkerId::AB => "AB",
+            TalkerId::AD => "AD",
+            TalkerId::AI => "AI",
+            TalkerId::AN => "AN",
+            TalkerId::AR => "AR",
+            TalkerId::AS => "AS",
+            TalkerId::AT => "AT",
+            TalkerId::AX => "AX",
+            TalkerId::BS => "BS",
+            TalkerId::SA => "SA",
+            TalkerId::Unknown => return Err("Unknown talker ID".into()),
+        };
+        let report_type = match self.report_type {
+            AisReportType::VDM => "VDM",
+            AisReportType::VDO => "VDO",
+            AisReportType::Unknown => return Err("Unknown report type".into()),
+        };
+        if data.is_empty() || self.max_payload_size == 0 {
+            return Err("Nothing to build a sentence from".into());
+        }
+        if fill_bits > 5 {
+            return Err("Too many fill bits".into());
+        }
+        for byte in data {
+            messages::sixbit_symbol(*byte)?;
+        }
+        let num_fragments = data.len().div_ceil(self.max_payload_size);
+        if num_fragments > MAX_FRAGMENTS {
+            return Err("Payload needs too many fragments".into());
+        }
+        let message_id = (num_fragments > 1).then(|| {
+            let message_id = self.message_id;
+            self.message_id = (self.message_id + 1) % 10;
+            message_id
+        });
+        let mut lines = SentenceLines::new();
+        for (index, payload) in data.chunks(self.max_payload_size).enumerate() {
+            let fragment_fill_bits = if index + 1 == num_fragments {
+                fill_bits
+            } else {
+                0
+            };
+            // Armored data is always ASCII
+            let payload = lib::std::str::from_utf8(payload)
+                .map_err(|_| Error::from("Payload is not ASCII"))?;
+            let mut sentence = lib::std::string::String::new();
+            write!(
+                sentence,
+                "!{}{},{},{},",
+                talker_id,
+                report_type,
+                num_fragments,
+                index + 1
+            )
+            .and_then(|_| match message_id {
+                Some(message_id) => write!(sentence, "{}", message_id),
+                None => Ok(()),
+            })
+            .and_then(|_| match self.channel {
+                Some(channel) => write!(sentence, ",{}", channel),
+                None => write!(sentence, ","),
+            })
+            .and_then(|_| write!(sentence, ",{},{}", payload, fragment_fill_bits))
+            .and_then(|_| {
+                // The checksum covers everything after the start delimiter
+                let checksum = nmea_checksum(&sentence.as_bytes()[1..]);
+                write!(sentence, "*{:02X}", checksum)
+            })
+            .map_err(|_| Error::from("Sentence too long"))?;
+            #[cfg(any(feature = "std", feature = "alloc"))]
+            lines.push(sentence);
+            #[cfg(all(not(feature = "std"), not(feature = "alloc")))]
+            lines
+                .push(sentence)
+                .map_err(|_| Error::from("Too many fragments"))?;
+        }
+        Ok(lines)
+    }
+}
+
+/// Computes the NMEA checksum of `data`, which is the XOR of all its bytes. For a
+/// sentence, this covers everything between the `!` or `$` and the `*`.
+pub fn nmea_checksum(data: &[u8]) -> u8 {
+    data.iter().fold(0u8, |acc, &item| acc ^ item)
 }
 
 /// Converts bytes representing an ASCII number to a string slice
@@ -222,6 +1024,13 @@ fn parse_u8_digit(data: &[u8]) -> IResult<&[u8], u8> {
     map_res(parse_numeric_string, lib::std::str::FromStr::from_str)(data)
 }
 
+/// Parses the message type, which is the first 6-bit symbol of the armored payload
+fn parse_message_type(data: &[u8]) -> IResult<&[u8], u8> {
+    map_res(take(1usize), |symbol: &[u8]| {
+        messages::sixbit_symbol(symbol[0])
+    })(data)
+}
+
 /// Named parser for the AIS portion of an NMEA sentence
 fn parse_ais_sentence(data: &[u8]) -> IResult<&[u8], AisSentence> {
     let (data, talker_id) = map(take(2u8), Into::into)(data)?;
@@ -239,7 +1048,7 @@ fn parse_ais_sentence(data: &[u8]) -> IResult<&[u8], AisSentence> {
     let (data, ais_data) = take_until(",")(data)?;
     let (data, _) = tag(",")(data)?;
     let (data, fill_bit_count) = verify(parse_u8_digit, |val| *val < 6)(data)?;
-    let (_, message_type) = messages::message_type(ais_data)?;
+    let (_, message_type) = parse_message_type(ais_data)?;
     #[cfg(any(feature = "std", feature = "alloc"))]
     let ais_data_owned = ais_data.into();
     #[cfg(all(not(feature = "std"), not(feature = "alloc")))]
@@ -262,15 +1071,38 @@ fn parse_ais_sentence(data: &[u8]) -> IResult<&[u8], AisSentence> {
             fill_bit_count,
             message_type,
             message: None,
+            sequence: None,
         },
     ))
 }
 
+/// Skips any noise (as seen on some serial feeds) ahead of the first tag block or
+/// sentence start delimiter, up to `MAX_LEADING_JUNK_BYTES`
+fn skip_leading_junk(data: &[u8]) -> IResult<&[u8], &[u8]> {
+    verify(
+        take_till(|c| c == b'\\' || c == b'!' || c == b'$'),
+        |junk: &[u8]| junk.len() <= MAX_LEADING_JUNK_BYTES,
+    )(data)
+}
+
+/// Named parser for any tag blocks ahead of a sentence, returned together, after
+/// skipping leading noise
+fn parse_tag_blocks(data: &[u8]) -> IResult<&[u8], &[u8]> {
+    let (data, _) = skip_leading_junk(data)?;
+    // Some gateways stack several tag blocks ahead of the sentence
+    recognize(many0_count(delimited(
+        tag("\\"),
+        take_until("\\"),
+        tag("\\"),
+    )))(data)
+}
+
 /// Named parser for an overall NMEA 0183 sentence
 fn parse_nmea_sentence(data: &[u8]) -> IResult<&[u8], (&[u8], AisSentence, u8)> {
-    let (data, _) = opt(delimited(tag("\\"), take_until("\\"), tag("\\")))(data)?;
+    let (data, _) = parse_tag_blocks(data)?;
     let (data, _) = alt((tag("!"), tag("$")))(data)?;
-    let (data, raw) = peek(take_until("*"))(data)?;
+    // A sentence cut off before its checksum is incomplete, rather than malformed
+    let (data, raw) = peek(nom::bytes::streaming::take_until("*"))(data)?;
     let (data, msg) = terminated(parse_ais_sentence, tag("*"))(data)?;
     let (data, checksum) = verify(hex_u32, |val| val <= &0xff)(data)?;
     Ok((data, (raw, msg, checksum as u8)))
@@ -289,11 +1121,30 @@ mod tests {
     const FRAGMENT_1: &[u8] =
         b"!AIVDM,2,1,1,B,53`soB8000010KSOW<0P4eDp4l6000000000000U0p<24t@P05H3S833CDP00000,0*78";
     const FRAGMENT_2: &[u8] = b"!AIVDM,2,2,1,B,0000000,2*26";
+    const ATON_TALKER: &[u8] =
+        b"!ANVDM,1,1,,B,E>kb9O9aS@7PUh10dh19@;0Tah2cWrfP:l?M`00003vP100,0*06";
+    const FRAGMENT_1_MESSAGE_2: &[u8] =
+        b"!AIVDM,2,1,2,B,53`soB8000010KSOW<0P4eDp4l6000000000000U0p<24t@P05H3S833CDP00000,0*7B";
+    const FRAGMENT_2_MESSAGE_2: &[u8] = b"!AIVDM,2,2,2,B,0000000,2*25";
+    const FRAGMENT_WITHOUT_MESSAGE_ID: &[u8] =
+        b"!AIVDM,2,1,,B,53`soB8000010KSOW<0P4eDp4l6000000000000U0p<24t@P05H3S833CDP00000,0*49";
+    const FRAGMENT_1_CHANNEL_A: &[u8] =
+        b"!AIVDM,2,1,1,A,53`soB8000010KSOW<0P4eDp4l6000000000000U0p<24t@P05H3S833CDP00000,0*7B";
+    const FRAGMENT_2_CHANNEL_A: &[u8] = b"!AIVDM,2,2,1,A,0000000,2*25";
     const NO_CHANNEL: &[u8] = b"!AIVDM,1,1,,,34RvgN500005tLTMfjiTs3u`0>`<,0*7A";
     const AIS_START_IDX: usize = 14;
     const AIS_END_IDX: usize = 61;
     const WITH_TAG_BLOCK: &[u8] =
         b"\\s:2573345,c:1696241893*00\\!AIVDM,1,1,,A,E>kb9I99S@0`8@:9ah;0TahI7@@;V4=v:nv;h00003vP100,0*7A";
+    const WITH_STACKED_TAG_BLOCKS: &[u8] =
+        b"\\s:2573345,c:1696241893*00\\\\g:1-2-1234*00\\!AIVDM,1,1,,A,E>kb9I99S@0`8@:9ah;0TahI7@@;V4=v:nv;h00003vP100,0*7A";
+    const WITH_TRAILING_TAG_BLOCK: &[u8] =
+        b"!AIVDM,1,1,,A,E>kb9I99S@0`8@:9ah;0TahI7@@;V4=v:nv;h00003vP100,0*7A\\s:2573345,c:1696241893*00\\";
+    const WITH_LEADING_JUNK: &[u8] =
+        b"garbage!AIVDM,1,1,,A,E>kb9I99S@0`8@:9ah;0TahI7@@;V4=v:nv;h00003vP100,0*7A";
+    const WITH_TOO_MUCH_LEADING_JUNK: &[u8] =
+        b"garbagegarbagegarbagegarbagegarbage!AIVDM,1,1,,A,E>kb9I99S@0`8@:9ah;0TahI7@@;V4=v:nv;h00003vP100,0*7A";
+    const MANEUVER_OUT_OF_SPEC: &[u8] = b"!AIVDM,1,1,,A,33nQ:B50000FiEBRjpcK19qSR>`<,0*06";
     const WITH_BAD_TAG_BLOCK: &[u8] =
         b"s:2573345,c:1696241893*00\\!AIVDM,1,1,,A,E>kb9I99S@0`8@:9ah;0TahI7@@;V4=v:nv;h00003vP100,0*7A";
 
@@ -317,8 +1168,9 @@ mod tests {
                     .try_into()
                     .unwrap(),
                 fill_bit_count: 0,
-                message_type: 17,
+                message_type: 21,
                 message: None,
+                sequence: None,
             }
         );
     }
@@ -348,8 +1200,9 @@ mod tests {
                     .try_into()
                     .unwrap(),
                 fill_bit_count: 0,
-                message_type: 17,
+                message_type: 21,
                 message: None,
+                sequence: None,
             }
         );
         assert_eq!((result.1).2, 122);
@@ -358,7 +1211,7 @@ mod tests {
     #[test]
     fn parse_using_struct_valid() {
         let mut parser = AisParser::new();
-        let result = parser.parse(GOOD_CHECKSUM, false).unwrap();
+        let result = parser.parse(GOOD_CHECKSUM, false).unwrap().1;
         assert_eq!(
             result,
             AisFragments::Complete(AisSentence {
@@ -375,8 +1228,9 @@ mod tests {
                     .try_into()
                     .unwrap(),
                 fill_bit_count: 0,
-                message_type: 17,
+                message_type: 21,
                 message: None,
+                sequence: None,
             })
         );
     }
@@ -388,6 +1242,20 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn parse_lowercase_checksum() {
+        let line = b"!AIVDM,1,1,,A,E>kb9I99S@0`8@:9ah;0TahI7@@;V4=v:nv;h00003vP100,0*7a";
+        assert_eq!(
+            &line[..line.len() - 2],
+            &GOOD_CHECKSUM[..GOOD_CHECKSUM.len() - 2]
+        );
+        let mut parser = AisParser::new();
+        assert_eq!(
+            parser.parse(line, true).unwrap().1,
+            parser.parse(GOOD_CHECKSUM, true).unwrap().1
+        );
+    }
+
     #[test]
     fn parse_invalid_checksum() {
         let mut parser = AisParser::new();
@@ -395,11 +1263,324 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn parse_group_assignment_command() {
+        let mut parser = AisParser::new();
+        let result = parser
+            .parse(b"!AIVDM,1,1,,A,G02OHAP8aLvg@@b1tF600000;00,0*2D", true)
+            .unwrap()
+            .1;
+        let sentence = Option::<AisSentence>::from(result).unwrap();
+        assert!(matches!(
+            sentence.message,
+            Some(AisMessage::GroupAssignmentCommand(_))
+        ));
+    }
+
+    #[test]
+    fn checksum_of_payload() {
+        let payload = &GOOD_CHECKSUM[1..GOOD_CHECKSUM.len() - 3];
+        assert_eq!(nmea_checksum(payload), 0x7a);
+        assert_eq!(nmea_checksum(b""), 0);
+    }
+
+    #[test]
+    fn build_single_sentence() {
+        let mut builder = AisSentenceBuilder::new(TalkerId::AI, Some('A'));
+        let lines = builder
+            .build_sentence(&GOOD_CHECKSUM[AIS_START_IDX..AIS_END_IDX], 0)
+            .unwrap();
+        assert_eq!(lines.len(), 1);
+        assert_eq!(lines[0].as_bytes(), GOOD_CHECKSUM);
+        let sentence = AisParser::new()
+            .parse(lines[0].as_bytes(), false)
+            .unwrap()
+            .1;
+        assert!(matches!(sentence, AisFragments::Complete(_)));
+    }
+
+    #[test]
+    fn build_fragmented_sentence() {
+        let data = b"53`soB8000010KSOW<0P4eDp4l6000000000000U0p<24t@P05H3S833CDP000000000000";
+        let mut builder = AisSentenceBuilder::new(TalkerId::AI, Some('B')).max_payload_size(60);
+        let mut parser = AisParser::new();
+        for message_id in 0..2 {
+            let lines = builder.build_sentence(data, 2).unwrap();
+            assert_eq!(lines.len(), 2);
+            let fragment = parser.parse(lines[0].as_bytes(), false).unwrap().1;
+            match fragment {
+                AisFragments::Incomplete(sentence) => {
+                    assert_eq!(sentence.fragment_number, 1);
+                    assert_eq!(sentence.message_id, Some(message_id));
+                    assert_eq!(sentence.fill_bit_count, 0);
+                }
+                _ => panic!("Expected an incomplete sentence"),
+            }
+            let sentence: Result<AisSentence> =
+                parser.parse(lines[1].as_bytes(), true).unwrap().1.into();
+            let sentence = sentence.unwrap();
+            assert_eq!(sentence.num_fragments, 2);
+            assert_eq!(sentence.fill_bit_count, 2);
+            assert_eq!(&sentence.data[..], data);
+            assert!(matches!(
+                sentence.message,
+                Some(AisMessage::StaticAndVoyageRelatedData(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn build_invalid_sentence() {
+        let mut builder = AisSentenceBuilder::new(TalkerId::AI, None);
+        assert!(builder.build_sentence(b"", 0).is_err());
+        assert!(builder.build_sentence(b"13u?,", 0).is_err());
+        assert!(builder.build_sentence(b"13u?", 6).is_err());
+        let mut builder = AisSentenceBuilder::new(TalkerId::Unknown, None);
+        assert!(builder.build_sentence(b"13u?", 0).is_err());
+        let mut builder = AisSentenceBuilder::new(TalkerId::AI, None).max_payload_size(1);
+        assert!(builder.build_sentence(b"13u?etPv2;", 0).is_err());
+    }
+
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    #[test]
+    fn parse_with_custom_handler() {
+        use crate::messages::bit_writer::BitWriter;
+        use crate::messages::custom::CustomMessage;
+
+        #[derive(Debug, PartialEq)]
+        struct Reserved {
+            spare: u8,
+        }
+
+        let mut parser = AisParser::new().register_handler(
+            28,
+            lib::std::boxed::Box::new(|data: &[u8]| {
+                Ok(CustomMessage::new(Reserved {
+                    spare: data[4] & 0x3,
+                }))
+            }),
+        );
+        let mut message = BitWriter::new();
+        message.u32(28, 6).u32(0, 2).u32(366999712, 30).u32(2, 2);
+        let (armored, fill_bits) = messages::armor(message.as_bytes(), message.bit_len()).unwrap();
+        let lines = AisSentenceBuilder::new(TalkerId::AI, Some('A'))
+            .build_sentence(&armored, fill_bits)
+            .unwrap();
+        let sentence = match parser.parse(lines[0].as_bytes(), true).unwrap() {
+            (_, AisFragments::Complete(sentence)) => sentence,
+            _ => panic!("Expected a complete sentence"),
+        };
+        let message = sentence.message.unwrap();
+        assert_eq!(message.message_type(), 28);
+        assert_eq!(message.mmsi(), 366999712);
+        match message {
+            AisMessage::Custom(ref custom) => {
+                assert_eq!(custom.downcast_ref(), Some(&Reserved { spare: 2 }))
+            }
+            _ => panic!("Expected a custom message"),
+        }
+        assert_eq!(
+            message.as_any().downcast_ref(),
+            Some(&Reserved { spare: 2 })
+        );
+
+        // Other message types are still decoded as usual
+        let sentence = parser.parse(GOOD_CHECKSUM, true).unwrap().1;
+        assert!(matches!(
+            sentence,
+            AisFragments::Complete(AisSentence {
+                message: Some(AisMessage::AidToNavigationReport(_)),
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn parse_with_sequence_numbers() {
+        let mut parser = AisParser::new().sequence_numbers(true);
+        let lines = [GOOD_CHECKSUM, FRAGMENT_1, FRAGMENT_2, NO_CHANNEL];
+        let mut sequences = heapless::Vec::<u64, 4>::new();
+        for line in lines {
+            match parser.parse(line, true).unwrap().1 {
+                AisFragments::Complete(sentence) => {
+                    sequences.push(sentence.sequence.unwrap()).unwrap()
+                }
+                AisFragments::Incomplete(sentence) | AisFragments::Filtered(sentence) => {
+                    assert_eq!(sentence.sequence, None)
+                }
+            }
+        }
+        assert_eq!(sequences, [0, 1, 2]);
+        let sentence: Option<AisSentence> = AisParser::new()
+            .parse(GOOD_CHECKSUM, true)
+            .unwrap()
+            .1
+            .into();
+        assert_eq!(sentence.unwrap().sequence, None);
+    }
+
+    #[test]
+    fn parse_with_bbox_filter() {
+        // Baltic Sea
+        let mut parser = AisParser::new().bbox_filter(54.0, 10.0, 60.0, 20.0);
+        let inside = b"!AIVDM,1,1,,A,13u?etPv2;0n:dDPwUM1U1Cb069D,0*24";
+        let outside = b"!AIVDM,1,1,,A,B6:hQDh0029Pt<4TAS003h6TSP00,0*26";
+        match parser.parse(inside, true).unwrap().1 {
+            AisFragments::Complete(sentence) => {
+                assert_eq!(sentence.message.unwrap().mmsi(), 265547250)
+            }
+            other => panic!("Expected a complete sentence, got {:?}", other),
+        }
+        match parser.parse(outside, true).unwrap().1 {
+            AisFragments::Filtered(sentence) => {
+                assert!(matches!(
+                    sentence.message,
+                    Some(AisMessage::StandardClassBPositionReport(_))
+                ))
+            }
+            other => panic!("Expected a filtered sentence, got {:?}", other),
+        }
+        let sentence: Option<AisSentence> = parser.parse(outside, true).unwrap().1.into();
+        assert_eq!(sentence, None);
+        // Messages without a position pass through
+        parser.parse(FRAGMENT_1, true).unwrap();
+        assert!(matches!(
+            parser.parse(FRAGMENT_2, true).unwrap().1,
+            AisFragments::Complete(_)
+        ));
+        // Undecoded messages aren't checked
+        assert!(matches!(
+            parser.parse(outside, false).unwrap().1,
+            AisFragments::Complete(_)
+        ));
+    }
+
+    #[cfg(feature = "profiling")]
+    #[test]
+    fn parse_with_timing_stats() {
+        let mut parser = AisParser::new();
+        for line in [
+            GOOD_CHECKSUM,
+            NO_CHANNEL,
+            FRAGMENT_1,
+            FRAGMENT_2,
+            GOOD_CHECKSUM,
+        ] {
+            parser.parse(line, true).unwrap();
+        }
+        // Not decoded
+        parser.parse(ATON_TALKER, false).unwrap();
+        let stats = parser.timing_stats();
+        assert_eq!(stats.get(21).unwrap().count, 2);
+        assert_eq!(stats.get(3).unwrap().count, 1);
+        assert_eq!(stats.get(5).unwrap().count, 1);
+        assert_eq!(
+            stats
+                .iter()
+                .map(|(message_type, _)| message_type)
+                .collect::<Vec<_>>(),
+            [3, 5, 21]
+        );
+        assert!(stats.get(21).unwrap().mean().is_some());
+    }
+
+    #[test]
+    fn decode_position_report_with_metadata() {
+        let line = b"\\s:2573345,c:1696241893*00\\!AIVDM,1,1,,A,13u?etPv2;0n:dDPwUM1U1Cb069D,0*24";
+        let decoded = decode_with_metadata(line).unwrap();
+        assert_eq!(decoded.tag_blocks.len(), 1);
+        let tag_block = &decoded.tag_blocks[0];
+        assert_eq!(tag_block.source_station.as_deref(), Some("2573345"));
+        assert_eq!(tag_block.receiver_timestamp, Some(1696241893));
+        assert_eq!(decoded.sentence.message_type, 1);
+        assert_eq!(decoded.sentence.channel, Some('A'));
+        assert_eq!(decoded.sentence.num_fragments, 1);
+        assert_eq!(decoded.sentence.message, None);
+        match decoded.message {
+            AisMessage::PositionReport(report) => assert_eq!(report.mmsi, 265547250),
+            other => panic!("Expected position report, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn long_range_broadcast_from_satellite() {
+        let line = b"\\s:SAT-23,c:1696241893*00\\!AIVDM,1,1,,B,KC5E2b@U19PFdLbMuc5=ROv62<7m,0*16";
+        let decoded = decode_with_metadata(line).unwrap();
+        assert!(matches!(
+            decoded.message,
+            AisMessage::LongRangeAisBroadcastMessage(_)
+        ));
+        assert!(decoded.tag_blocks[0].is_satellite_source());
+    }
+
+    #[test]
+    fn decode_fragment_with_metadata() {
+        assert_eq!(
+            decode_with_metadata(FRAGMENT_1),
+            Err(Error::from("Incomplete message"))
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn decode_file() {
+        use std::io::Write;
+
+        let path =
+            std::env::temp_dir().join(format!("ais-decode-file-{}.nmea", std::process::id()));
+        let mut file = std::fs::File::create(&path).unwrap();
+        for line in [WITH_TAG_BLOCK, b"", BAD_CHECKSUM, FRAGMENT_1, FRAGMENT_2] {
+            file.write_all(line).unwrap();
+            file.write_all(b"\r\n").unwrap();
+        }
+        drop(file);
+        let results = decode_file_to_vec(&path);
+        std::fs::remove_file(&path).unwrap();
+        let results = results.unwrap();
+        assert_eq!(results.len(), 3);
+        let (tag_blocks, sentence) = results[0].as_ref().unwrap();
+        assert_eq!(tag_blocks[0].receiver_timestamp, Some(1696241893));
+        assert!(matches!(
+            sentence.message,
+            Some(AisMessage::AidToNavigationReport(_))
+        ));
+        assert!(matches!(results[1], Err(Error::Checksum { .. })));
+        let (tag_blocks, sentence) = results[2].as_ref().unwrap();
+        assert!(tag_blocks.is_empty());
+        assert!(matches!(
+            sentence.message,
+            Some(AisMessage::StaticAndVoyageRelatedData(_))
+        ));
+        assert!(decode_file_to_vec(&path).is_err());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn decode_from_reader() {
+        let mut data = Vec::new();
+        for line in [WITH_TAG_BLOCK, b"", BAD_CHECKSUM, FRAGMENT_1, FRAGMENT_2] {
+            data.extend_from_slice(line);
+            data.push(b'\n');
+        }
+        let mut parser = AisParser::new();
+        let results: Vec<_> = decode_reader(&data[..], &mut parser).collect();
+        assert_eq!(results.len(), 3);
+        assert!(matches!(
+            results[0].as_ref().unwrap().message,
+            Some(AisMessage::AidToNavigationReport(_))
+        ));
+        assert!(matches!(results[1], Err(Error::Checksum { .. })));
+        assert!(matches!(
+            results[2].as_ref().unwrap().message,
+            Some(AisMessage::StaticAndVoyageRelatedData(_))
+        ));
+    }
+
     #[test]
     fn parse_multiple_fragments() {
         let mut parser = AisParser::new();
-        let frag1 = parser.parse(FRAGMENT_1, false).unwrap();
-        let frag2 = parser.parse(FRAGMENT_2, false).unwrap();
+        let frag1 = parser.parse(FRAGMENT_1, false).unwrap().1;
+        let frag2 = parser.parse(FRAGMENT_2, false).unwrap().1;
         if let AisFragments::Complete(_) = frag1 {
             panic!("Expected frag1 to be incomplete, but it was {:?}", frag1);
         }
@@ -410,6 +1591,257 @@ mod tests {
         }
     }
 
+    #[test]
+    fn station_kind() {
+        let mut parser = AisParser::new();
+        let sentence: Result<AisSentence> = parser.parse(ATON_TALKER, false).unwrap().1.into();
+        let sentence = sentence.unwrap();
+        assert_eq!(sentence.talker_id, TalkerId::AN);
+        assert_eq!(sentence.source_station_kind(), StationKind::AidToNavigation);
+
+        let sentence: Result<AisSentence> = parser.parse(GOOD_CHECKSUM, false).unwrap().1.into();
+        assert_eq!(
+            sentence.unwrap().source_station_kind(),
+            StationKind::AidToNavigation
+        );
+        let sentence: Result<AisSentence> = parser.parse(NO_CHANNEL, false).unwrap().1.into();
+        assert_eq!(sentence.unwrap().source_station_kind(), StationKind::ClassA);
+    }
+
+    #[test]
+    fn parse_dropped_fragments() {
+        use core::sync::atomic::{AtomicU8, Ordering};
+        static DROPPED_ID: AtomicU8 = AtomicU8::new(0);
+        static DROPPED_COUNT: AtomicU8 = AtomicU8::new(0);
+
+        let mut parser = AisParser::new().on_dropped_fragments(|message_id, fragments| {
+            DROPPED_ID.store(message_id.unwrap(), Ordering::SeqCst);
+            DROPPED_COUNT.store(fragments, Ordering::SeqCst);
+        });
+        // Completed sets are not reported
+        parser.parse(FRAGMENT_1, false).unwrap();
+        parser.parse(FRAGMENT_2, false).unwrap();
+        parser.parse(FRAGMENT_1, false).unwrap();
+        assert_eq!(DROPPED_COUNT.load(Ordering::SeqCst), 0);
+        // Other messages are reassembled alongside it
+        parser.parse(FRAGMENT_1_MESSAGE_2, false).unwrap();
+        assert_eq!(DROPPED_COUNT.load(Ordering::SeqCst), 0);
+        // The message starts again before it completed
+        parser.parse(FRAGMENT_1, false).unwrap();
+        assert_eq!(DROPPED_ID.load(Ordering::SeqCst), 1);
+        assert_eq!(DROPPED_COUNT.load(Ordering::SeqCst), 1);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn parse_dropped_fragments_with_closure() {
+        use std::sync::{Arc, Mutex};
+
+        let dropped = Arc::new(Mutex::new(Vec::new()));
+        let mut parser = AisParser::with_timeout(1).on_dropped_fragments({
+            let dropped = Arc::clone(&dropped);
+            move |message_id, fragments| dropped.lock().unwrap().push((message_id, fragments))
+        });
+        parser.parse(FRAGMENT_1, false).unwrap();
+        parser.parse(FRAGMENT_1_MESSAGE_2, false).unwrap();
+        parser.parse(FRAGMENT_1, false).unwrap();
+        assert_eq!(*dropped.lock().unwrap(), [(Some(1), 1)]);
+        parser.parse(GOOD_CHECKSUM, false).unwrap();
+        parser.parse(GOOD_CHECKSUM, false).unwrap();
+        assert_eq!(
+            *dropped.lock().unwrap(),
+            [(Some(1), 1), (Some(2), 1), (Some(1), 1)]
+        );
+    }
+
+    #[test]
+    fn parse_too_many_interleaved_fragments() {
+        use core::sync::atomic::{AtomicU8, Ordering};
+        static DROPPED_ID: AtomicU8 = AtomicU8::new(u8::MAX);
+
+        let mut parser = AisParser::new().on_dropped_fragments(|message_id, _| {
+            DROPPED_ID.store(message_id.unwrap(), Ordering::SeqCst);
+        });
+        let mut builder = AisSentenceBuilder::new(TalkerId::AI, Some('A')).max_payload_size(4);
+        for _ in 0..MAX_FRAGMENT_ASSEMBLIES {
+            let lines = builder.build_sentence(b"13u?etPv", 0).unwrap();
+            parser.parse(lines[0].as_bytes(), false).unwrap();
+        }
+        assert_eq!(DROPPED_ID.load(Ordering::SeqCst), u8::MAX);
+        let lines = builder.build_sentence(b"13u?etPv", 0).unwrap();
+        parser.parse(lines[0].as_bytes(), false).unwrap();
+        // The oldest message is dropped
+        assert_eq!(DROPPED_ID.load(Ordering::SeqCst), 0);
+        assert!(parser.parse(lines[1].as_bytes(), false).is_ok());
+    }
+
+    #[test]
+    fn parse_with_fragment_timeout() {
+        use core::sync::atomic::{AtomicU8, Ordering};
+        static DROPPED_COUNT: AtomicU8 = AtomicU8::new(0);
+
+        let mut parser = AisParser::with_timeout(3).on_dropped_fragments(|_, _| {
+            DROPPED_COUNT.fetch_add(1, Ordering::SeqCst);
+        });
+        // The final fragment of this message is lost
+        parser.parse(FRAGMENT_1_CHANNEL_A, false).unwrap();
+        for _ in 0..3 {
+            parser.parse(GOOD_CHECKSUM, false).unwrap();
+        }
+        assert_eq!(DROPPED_COUNT.load(Ordering::SeqCst), 0);
+        parser.parse(GOOD_CHECKSUM, false).unwrap();
+        assert_eq!(DROPPED_COUNT.load(Ordering::SeqCst), 1);
+        // A new message reuses the message ID, on the other channel, so would
+        // otherwise be taken for a copy of the lost one
+        assert!(matches!(
+            parser.parse(FRAGMENT_1, false).unwrap().1,
+            AisFragments::Incomplete(_)
+        ));
+        let sentence: Option<AisSentence> = parser.parse(FRAGMENT_2, true).unwrap().1.into();
+        let sentence = sentence.unwrap();
+        assert_eq!(sentence.channel, Some('B'));
+        assert!(matches!(
+            sentence.message,
+            Some(AisMessage::StaticAndVoyageRelatedData(_))
+        ));
+    }
+
+    #[test]
+    fn parse_after_reset() {
+        let mut parser = AisParser::new();
+        parser.parse(FRAGMENT_1_CHANNEL_A, false).unwrap();
+        parser.reset();
+        parser.parse(FRAGMENT_1, false).unwrap();
+        let sentence: Option<AisSentence> = parser.parse(FRAGMENT_2, false).unwrap().1.into();
+        assert_eq!(sentence.unwrap().channel, Some('B'));
+        // Nothing is left of the completed message
+        assert!(parser.parse(FRAGMENT_2, false).is_err());
+    }
+
+    #[test]
+    fn parse_interleaved_fragments() {
+        let mut parser = AisParser::new();
+        let mut completed = heapless::Vec::<Option<u8>, 2>::new();
+        for line in [
+            FRAGMENT_1,
+            FRAGMENT_1_MESSAGE_2,
+            FRAGMENT_2,
+            FRAGMENT_2_MESSAGE_2,
+        ] {
+            if let AisFragments::Complete(sentence) = parser.parse(line, true).unwrap().1 {
+                assert!(matches!(
+                    sentence.message,
+                    Some(AisMessage::StaticAndVoyageRelatedData(_))
+                ));
+                completed.push(sentence.message_id).unwrap();
+            }
+        }
+        assert_eq!(completed, [Some(1), Some(2)]);
+    }
+
+    #[test]
+    fn parse_interleaved_fragments_on_both_channels() {
+        // Another type 5 message, with a different MMSI, but the same message ID
+        let data = b"53`soC8000010KSOW<0P4eDp4l6000000000000U0p<24t@P05H3S833CDP000000000000";
+        let mut builder = AisSentenceBuilder::new(TalkerId::AI, Some('B')).max_payload_size(60);
+        builder.build_sentence(data, 2).unwrap();
+        let lines = builder.build_sentence(data, 2).unwrap();
+        let mut parser = AisParser::new();
+        let mut completed = heapless::Vec::<(Option<char>, u32), 4>::new();
+        for line in [
+            FRAGMENT_1_CHANNEL_A,
+            lines[0].as_bytes(),
+            FRAGMENT_2_CHANNEL_A,
+            lines[1].as_bytes(),
+            // A copy of the first message, which is dropped
+            FRAGMENT_1,
+            FRAGMENT_2,
+            // The copy has been seen, so the same message is decoded again
+            FRAGMENT_1_CHANNEL_A,
+            FRAGMENT_2_CHANNEL_A,
+        ] {
+            if let AisFragments::Complete(sentence) = parser.parse(line, true).unwrap().1 {
+                completed
+                    .push((sentence.channel, sentence.message.unwrap().mmsi()))
+                    .unwrap();
+            }
+        }
+        assert_eq!(completed.len(), 3);
+        assert_eq!(completed[0].0, Some('A'));
+        assert_eq!(completed[1].0, Some('B'));
+        assert_ne!(completed[0].1, completed[1].1);
+        assert_eq!(completed[2], completed[0]);
+    }
+
+    #[test]
+    fn parse_truncated_sentence() {
+        let mut parser = AisParser::new();
+        let result = parser.parse(&GOOD_CHECKSUM[..40], true);
+        assert_eq!(result, Err(Error::Incomplete));
+        assert!(matches!(
+            parser.parse(BAD_STRUCTURE, true),
+            Err(Error::Nmea { .. })
+        ));
+    }
+
+    #[test]
+    fn parse_fragment_without_message_id() {
+        let mut parser = AisParser::new();
+        let err = parser
+            .parse(FRAGMENT_WITHOUT_MESSAGE_ID, false)
+            .unwrap_err();
+        assert_eq!(
+            err,
+            Error::from("Fragment of a multi-sentence message has no sequential message ID")
+        );
+    }
+
+    #[test]
+    fn parse_fragments_received_on_both_channels() {
+        let mut parser = AisParser::new();
+        let mut completed = 0;
+        for line in [
+            FRAGMENT_1_CHANNEL_A,
+            FRAGMENT_1,
+            FRAGMENT_2_CHANNEL_A,
+            FRAGMENT_2,
+        ] {
+            if let AisFragments::Complete(sentence) = parser.parse(line, true).unwrap().1 {
+                completed += 1;
+                assert_eq!(sentence.channel, Some('A'));
+                assert!(matches!(
+                    sentence.message,
+                    Some(AisMessage::StaticAndVoyageRelatedData(_))
+                ));
+            }
+        }
+        assert_eq!(completed, 1);
+    }
+
+    #[test]
+    fn parse_different_messages_with_same_id_on_both_channels() {
+        // Another type 5 message, with a different MMSI
+        let data = b"53`soC8000010KSOW<0P4eDp4l6000000000000U0p<24t@P05H3S833CDP000000000000";
+        let mut builder = AisSentenceBuilder::new(TalkerId::AI, Some('B')).max_payload_size(60);
+        builder.build_sentence(data, 2).unwrap();
+        let lines = builder.build_sentence(data, 2).unwrap();
+        let mut parser = AisParser::new();
+        let mut mmsis = heapless::Vec::<u32, 2>::new();
+        for line in [
+            FRAGMENT_1_CHANNEL_A,
+            FRAGMENT_2_CHANNEL_A,
+            lines[0].as_bytes(),
+            lines[1].as_bytes(),
+        ] {
+            if let AisFragments::Complete(sentence) = parser.parse(line, true).unwrap().1 {
+                assert_eq!(sentence.message_id, Some(1));
+                mmsis.push(sentence.message.unwrap().mmsi()).unwrap();
+            }
+        }
+        assert_eq!(mmsis.len(), 2);
+        assert_ne!(mmsis[0], mmsis[1]);
+    }
+
     #[test]
     fn test_talker_id_conversions() {
         assert_eq!(TalkerId::from(b"AI".as_ref()), TalkerId::AI);
@@ -444,15 +1876,140 @@ mod tests {
                     .try_into()
                     .unwrap(),
                 fill_bit_count: 0,
-                message_type: 17,
+                message_type: 21,
                 message: None,
+                sequence: None,
             }
         );
         assert_eq!((result.1).2, 122);
+        let (tag_blocks, sentence) = AisParser::new().parse(WITH_TAG_BLOCK, false).unwrap();
+        assert_eq!(tag_blocks.len(), 1);
+        assert_eq!(tag_blocks[0].source_station.as_deref(), Some("2573345"));
+        assert_eq!(tag_blocks[0].receiver_timestamp, Some(1696241893));
+        assert_eq!(tag_blocks[0].text, None);
+        assert!(matches!(sentence, AisFragments::Complete(_)));
+        // No tag block
+        let (tag_blocks, _) = AisParser::new().parse(GOOD_CHECKSUM, false).unwrap();
+        assert!(tag_blocks.is_empty());
+    }
+
+    #[test]
+    fn parse_valid_sentence_with_stacked_tag_blocks() {
+        let (remaining, (_, sentence, checksum)) =
+            parse_nmea_sentence(WITH_STACKED_TAG_BLOCKS).unwrap();
+        assert_eq!(remaining, b"");
+        assert_eq!(checksum, 0x7a);
+        assert_eq!(sentence.message_type, 21);
+        let (tag_blocks, _) = AisParser::new()
+            .parse(WITH_STACKED_TAG_BLOCKS, false)
+            .unwrap();
+        assert_eq!(tag_blocks.len(), 2);
+        assert_eq!(tag_blocks[0].source_station.as_deref(), Some("2573345"));
+        assert_eq!(tag_blocks[0].receiver_timestamp, Some(1696241893));
+        assert_eq!(tag_blocks[0].group, None);
+        assert_eq!(tag_blocks[1].group.unwrap().group_id, 1234);
+        assert_eq!(tag_blocks[1].source_station, None);
+    }
+
+    #[test]
+    fn parse_stacked_tag_blocks_with_repeated_field() {
+        let line = b"\\s:2573345,c:1696241893*00\\\\s:2573350,n:7*00\\!AIVDM,1,1,,A,E>kb9I99S@0`8@:9ah;0TahI7@@;V4=v:nv;h00003vP100,0*7A";
+        let (tag_blocks, _) = AisParser::new().parse(line, false).unwrap();
+        assert_eq!(tag_blocks.len(), 2);
+        // Both blocks set the source station, and neither value is lost
+        assert_eq!(tag_blocks[0].source_station.as_deref(), Some("2573345"));
+        assert_eq!(tag_blocks[0].receiver_timestamp, Some(1696241893));
+        assert_eq!(tag_blocks[0].line_count, None);
+        assert_eq!(tag_blocks[1].source_station.as_deref(), Some("2573350"));
+        assert_eq!(tag_blocks[1].receiver_timestamp, None);
+        assert_eq!(tag_blocks[1].line_count, Some(7));
+    }
+
+    #[cfg(all(not(feature = "std"), not(feature = "alloc")))]
+    #[test]
+    fn parse_too_many_tag_blocks() {
+        let line = b"\\n:1*00\\\\n:2*00\\\\n:3*00\\\\n:4*00\\\\n:5*00\\!AIVDM,1,1,,A,E>kb9I99S@0`8@:9ah;0TahI7@@;V4=v:nv;h00003vP100,0*7A";
+        assert_eq!(
+            AisParser::new().parse(line, false),
+            Err(Error::from("Too many tag blocks"))
+        );
+    }
+
+    #[test]
+    fn parse_tag_block_checksum_strict() {
+        let bad_checksum = b"\\s:2573345,c:1696241893*5A\\!AIVDM,1,1,,A,E>kb9I99S@0`8@:9ah;0TahI7@@;V4=v:nv;h00003vP100,0*7A";
+        assert!(AisParser::new().parse(bad_checksum, false).is_ok());
+        let mut parser = AisParser::new().strict(true);
+        assert_eq!(
+            parser.parse(bad_checksum, false),
+            Err(Error::Checksum {
+                expected: 0x5a,
+                found: 0x00,
+            })
+        );
+        let (tag_blocks, _) = parser.parse(WITH_TAG_BLOCK, false).unwrap();
+        assert_eq!(tag_blocks[0].receiver_timestamp, Some(1696241893));
+    }
+
+    #[test]
+    fn parse_sentence_with_malformed_tag_block_field() {
+        let line =
+            b"\\c:yesterday*00\\!AIVDM,1,1,,A,E>kb9I99S@0`8@:9ah;0TahI7@@;V4=v:nv;h00003vP100,0*7A";
+        assert_eq!(
+            AisParser::new().parse(line, false),
+            Err(Error::from("Invalid number in tag block"))
+        );
     }
 
     #[test]
     fn parse_sentence_with_invalid_tag_block() {
         assert!(parse_ais_sentence(&WITH_BAD_TAG_BLOCK[1..64]).is_err());
     }
+
+    #[test]
+    fn parse_sentence_with_trailing_tag_block() {
+        let mut parser = AisParser::new();
+        assert_eq!(
+            parser.parse(WITH_TRAILING_TAG_BLOCK, false),
+            Err(Error::from(
+                "Tag block follows the sentence, rather than preceding it"
+            ))
+        );
+        // Trailing whitespace is fine
+        let mut line = heapless::Vec::<u8, 96>::from_slice(GOOD_CHECKSUM).unwrap();
+        line.extend_from_slice(b"\r\n").unwrap();
+        assert!(parser.parse(&line, false).is_ok());
+    }
+
+    #[test]
+    fn parse_sentence_with_leading_junk() {
+        let (remaining, (_, sentence, checksum)) = parse_nmea_sentence(WITH_LEADING_JUNK).unwrap();
+        assert_eq!(remaining, b"");
+        assert_eq!(checksum, 0x7a);
+        assert_eq!(sentence.talker_id, TalkerId::AI);
+        assert_eq!(sentence.message_type, 21);
+        let mut parser = AisParser::new();
+        assert!(parser.parse(WITH_LEADING_JUNK, true).is_ok());
+    }
+
+    #[test]
+    fn parse_sentence_with_too_much_leading_junk() {
+        assert!(parse_nmea_sentence(WITH_TOO_MUCH_LEADING_JUNK).is_err());
+    }
+
+    #[test]
+    fn parse_out_of_spec_lenient() {
+        let mut parser = AisParser::new();
+        let result = parser.parse(MANEUVER_OUT_OF_SPEC, true).unwrap().1;
+        assert!(matches!(result, AisFragments::Complete(_)));
+    }
+
+    #[test]
+    fn parse_out_of_spec_strict() {
+        let mut parser = AisParser::new().strict(true);
+        assert!(parser.parse(MANEUVER_OUT_OF_SPEC, true).is_err());
+        // Undecoded sentences are not checked
+        assert!(parser.parse(MANEUVER_OUT_OF_SPEC, false).is_ok());
+        assert!(parser.parse(GOOD_CHECKSUM, true).is_ok());
+    }
 }
@@ -0,0 +1,152 @@
+//! Incremental decoding, one byte at a time
+use crate::errors::Result;
+use crate::sentence::{AisFragments, AisParser, AisSentence};
+
+/// Decodes AIS sentences from a byte stream, such as a UART, fed in one byte at a
+/// time. Bytes are collected into a fixed buffer of `BUF` bytes until a line ending
+/// is seen, so no allocation is needed; this makes it suitable for use from an
+/// interrupt handler or on `no_std` targets. Messages dropped by the parser's
+/// [`bbox_filter()`](../sentence/struct.AisParser.html#method.bbox_filter) are
+/// not returned.
+///
+/// # Example
+/// ```
+/// use ais::streaming::StreamingDecoder;
+///
+/// let mut decoder = StreamingDecoder::<128>::new();
+/// let input = b"!AIVDM,1,1,,A,403OtVAv6s5l1o?I``E`4I?02<34,0*21\r\n";
+/// let mut messages = 0;
+/// for byte in input {
+///     if let Some(sentence) = decoder.push_byte(*byte) {
+///         assert!(sentence?.message.is_some());
+///         messages += 1;
+///     }
+/// }
+/// assert_eq!(messages, 1);
+/// # Ok::<(), ais::errors::Error>(())
+/// ```
+#[derive(Debug, Default)]
+pub struct StreamingDecoder<const BUF: usize> {
+    line: heapless::Vec<u8, BUF>,
+    overflowed: bool,
+    parser: AisParser,
+}
+
+impl<const BUF: usize> StreamingDecoder<BUF> {
+    /// Creates a new `StreamingDecoder`, which decodes messages using a default `AisParser`
+    pub fn new() -> Self {
+        Self::with_parser(AisParser::new())
+    }
+
+    /// Creates a new `StreamingDecoder`, using a preconfigured `AisParser`
+    pub fn with_parser(parser: AisParser) -> Self {
+        Self {
+            line: heapless::Vec::new(),
+            overflowed: false,
+            parser,
+        }
+    }
+
+    /// Adds a byte to the current line. Once a line ending completes a sentence, the
+    /// sentence is parsed and decoded; the result is returned if the message is
+    /// complete, or if an error occurred. Fragments of a multi-sentence message
+    /// return `None` until the final fragment arrives.
+    ///
+    /// Lines longer than `BUF` bytes are discarded, returning an error at the line ending.
+    pub fn push_byte(&mut self, byte: u8) -> Option<Result<AisSentence>> {
+        if byte == b'\r' || byte == b'\n' {
+            let result = if self.overflowed {
+                Some(Err("Sentence exceeds buffer size".into()))
+            } else if self.line.is_empty() {
+                None
+            } else {
+                match self.parser.parse(&self.line, true) {
+                    Ok((_, AisFragments::Complete(sentence))) => Some(Ok(sentence)),
+                    Ok((_, AisFragments::Incomplete(_) | AisFragments::Filtered(_))) => None,
+                    Err(err) => Some(Err(err)),
+                }
+            };
+            self.line.clear();
+            self.overflowed = false;
+            result
+        } else {
+            if self.line.push(byte).is_err() {
+                self.overflowed = true;
+            }
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::messages::AisMessage;
+
+    const FRAGMENT_1: &[u8] =
+        b"!AIVDM,2,1,1,B,53`soB8000010KSOW<0P4eDp4l6000000000000U0p<24t@P05H3S833CDP00000,0*78\r\n";
+    const FRAGMENT_2: &[u8] = b"!AIVDM,2,2,1,B,0000000,2*26\r\n";
+
+    #[test]
+    fn byte_at_a_time() {
+        let mut decoder = StreamingDecoder::<96>::new();
+        let mut messages = 0;
+        for byte in FRAGMENT_1.iter().chain(FRAGMENT_2) {
+            if let Some(result) = decoder.push_byte(*byte) {
+                let sentence = result.unwrap();
+                assert!(matches!(
+                    sentence.message,
+                    Some(AisMessage::StaticAndVoyageRelatedData(_))
+                ));
+                messages += 1;
+            }
+        }
+        assert_eq!(messages, 1);
+    }
+
+    #[test]
+    fn bbox_filter() {
+        // Baltic Sea
+        let parser = AisParser::new().bbox_filter(54.0, 10.0, 60.0, 20.0);
+        let mut decoder = StreamingDecoder::<96>::with_parser(parser);
+        let inside = b"!AIVDM,1,1,,A,13u?etPv2;0n:dDPwUM1U1Cb069D,0*24\n";
+        let outside = b"!AIVDM,1,1,,A,B6:hQDh0029Pt<4TAS003h6TSP00,0*26\n";
+        let mut mmsis = heapless::Vec::<u32, 4>::new();
+        for byte in inside
+            .iter()
+            .chain(outside)
+            .chain(FRAGMENT_1)
+            .chain(FRAGMENT_2)
+        {
+            if let Some(result) = decoder.push_byte(*byte) {
+                mmsis.push(result.unwrap().message.unwrap().mmsi()).unwrap();
+            }
+        }
+        // The static data message has no position, so it passes through
+        assert_eq!(mmsis, [265547250, 244250440]);
+    }
+
+    #[test]
+    fn overflow() {
+        let mut decoder = StreamingDecoder::<64>::new();
+        let errors = FRAGMENT_1
+            .iter()
+            .filter_map(|byte| decoder.push_byte(*byte))
+            .filter(|result| result.is_err())
+            .count();
+        assert_eq!(errors, 1);
+        // Recovers on the next line
+        let line = b"!AIVDM,1,1,,A,403OtVAv6s5l1o?I``E`4I?02<34,0*21\n";
+        let results = line
+            .iter()
+            .filter_map(|byte| decoder.push_byte(*byte))
+            .collect::<heapless::Vec<_, 1>>();
+        assert!(matches!(
+            results[0],
+            Ok(AisSentence {
+                message: Some(AisMessage::BaseStationReport(_)),
+                ..
+            })
+        ));
+    }
+}
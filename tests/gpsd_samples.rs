@@ -0,0 +1,42 @@
+//! Decodes a corpus of sample sentences, covering every implemented message type
+use ais::messages::{self, supported_message_types};
+use ais::{AisFragments, AisParser};
+use std::collections::BTreeMap;
+
+const SAMPLES: &str = include_str!("data/sample.aivdm");
+
+#[test]
+fn decode_samples() {
+    let mut parser = AisParser::new();
+    let mut decoded: BTreeMap<u8, usize> = BTreeMap::new();
+    let mut unsupported: BTreeMap<u8, usize> = BTreeMap::new();
+    for (number, line) in SAMPLES.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let sentence = match parser.parse(line.as_bytes(), false) {
+            Ok((_, AisFragments::Complete(sentence))) => sentence,
+            Ok((_, AisFragments::Incomplete(_) | AisFragments::Filtered(_))) => continue,
+            Err(err) => panic!("line {}: {:?}: {}", number + 1, err, line),
+        };
+        let unarmored = messages::unarmor(&sentence.data, sentence.fill_bit_count as usize)
+            .unwrap_or_else(|err| panic!("line {}: {:?}: {}", number + 1, err, line));
+        let message_type = unarmored[0] >> 2;
+        if supported_message_types().contains(&message_type) {
+            if let Err(err) = messages::parse(&unarmored) {
+                panic!("line {}: {:?}: {}", number + 1, err, line);
+            }
+            *decoded.entry(message_type).or_default() += 1;
+        } else {
+            assert!(messages::parse(&unarmored).is_err());
+            *unsupported.entry(message_type).or_default() += 1;
+        }
+    }
+    println!("Decoded, by message type: {:?}", decoded);
+    println!("Unsupported, by message type: {:?}", unsupported);
+    // Every type reported as supported must have decoded at least once
+    for message_type in supported_message_types() {
+        assert!(decoded.contains_key(message_type), "type {}", message_type);
+    }
+}